@@ -1,28 +1,47 @@
 use crate::client::ClientConfig;
 use crate::codex as codex_cmds;
+use crate::gemini;
 use crate::json_manager::JsonManager;
+use crate::store::{store_for, ServerStore};
 use serde_json::Value;
 
 pub enum ClientAdapter<'a> {
     Json {
         client: &'a str,
         path: Option<&'a str>,
+        /// Revision the caller last read; the write is refused if the file moved on
+        revision: Option<&'a str>,
     },
     Codex,
+    Store(Box<dyn ServerStore>),
 }
 
 impl<'a> ClientAdapter<'a> {
     pub fn new(client: &'a str, path: Option<&'a str>) -> Self {
         if client == "codex" {
             ClientAdapter::Codex
+        } else if let Some(store) = store_for(client, path) {
+            ClientAdapter::Store(store)
         } else {
-            ClientAdapter::Json { client, path }
+            ClientAdapter::Json {
+                client,
+                path,
+                revision: None,
+            }
+        }
+    }
+
+    /// Only JSON-file clients hand out revisions, so other clients ignore it
+    pub fn with_revision(mut self, expected: Option<&'a str>) -> Self {
+        if let ClientAdapter::Json { revision, .. } = &mut self {
+            *revision = expected;
         }
+        self
     }
 
     fn json_path(&self) -> Option<(String, std::path::PathBuf)> {
         match self {
-            ClientAdapter::Json { client, path } => {
+            ClientAdapter::Json { client, path, .. } => {
                 let cfg = ClientConfig::new(client, *path);
                 Some((client.to_string(), cfg.get_path().to_path_buf()))
             }
@@ -30,6 +49,31 @@ impl<'a> ClientAdapter<'a> {
         }
     }
 
+    /// Translate a common server config into the client's own field names
+    fn client_config(&self, cfg: Value) -> Value {
+        match self {
+            ClientAdapter::Json {
+                client: "gemini", ..
+            } => gemini::common_to_gemini(cfg),
+            _ => cfg,
+        }
+    }
+
+    fn revision(&self) -> Option<&'a str> {
+        match self {
+            ClientAdapter::Json { revision, .. } => *revision,
+            _ => None,
+        }
+    }
+
+    async fn store_servers(store: &dyn ServerStore) -> Result<Value, String> {
+        Ok(serde_json::json!({"mcpServers": store.read_servers().await?}))
+    }
+
+    async fn store_disabled(store: &dyn ServerStore) -> Result<Value, String> {
+        Ok(Value::Object(store.list_disabled().await?))
+    }
+
     pub async fn add(&self, name: String, cfg: Value) -> Result<Value, String> {
         match self {
             ClientAdapter::Codex => {
@@ -41,6 +85,11 @@ impl<'a> ClientAdapter<'a> {
                 let servers = codex_cmds::read_mcp_servers().await?;
                 Ok(serde_json::json!({"mcpServers": servers}))
             }
+            ClientAdapter::Store(store) => {
+                println!("[Adapter][{}] add server: {}", store.client(), name);
+                store.upsert(&name, cfg).await?;
+                Self::store_servers(store.as_ref()).await
+            }
             ClientAdapter::Json { .. } => {
                 let (client_name, path) = self.json_path().unwrap();
                 println!(
@@ -49,7 +98,15 @@ impl<'a> ClientAdapter<'a> {
                     name,
                     path.display()
                 );
-                JsonManager::add_mcp_server(&path, client_name.as_str(), &name, cfg).await
+                let cfg = self.client_config(cfg);
+                JsonManager::add_mcp_server(
+                    &path,
+                    client_name.as_str(),
+                    &name,
+                    cfg,
+                    self.revision(),
+                )
+                .await
             }
         }
     }
@@ -62,6 +119,11 @@ impl<'a> ClientAdapter<'a> {
                 let servers = codex_cmds::read_mcp_servers().await?;
                 Ok(serde_json::json!({"mcpServers": servers}))
             }
+            ClientAdapter::Store(store) => {
+                println!("[Adapter][{}] remove server: {}", store.client(), name);
+                store.remove(&name).await?;
+                Self::store_servers(store.as_ref()).await
+            }
             ClientAdapter::Json { .. } => {
                 let (client_name, path) = self.json_path().unwrap();
                 println!(
@@ -70,7 +132,8 @@ impl<'a> ClientAdapter<'a> {
                     name,
                     path.display()
                 );
-                JsonManager::remove_mcp_server(&path, client_name.as_str(), &name).await
+                JsonManager::remove_mcp_server(&path, client_name.as_str(), &name, self.revision())
+                    .await
             }
         }
     }
@@ -86,6 +149,11 @@ impl<'a> ClientAdapter<'a> {
                 let servers = codex_cmds::read_mcp_servers().await?;
                 Ok(serde_json::json!({"mcpServers": servers}))
             }
+            ClientAdapter::Store(store) => {
+                println!("[Adapter][{}] update server: {}", store.client(), name);
+                store.upsert(&name, cfg).await?;
+                Self::store_servers(store.as_ref()).await
+            }
             ClientAdapter::Json { .. } => {
                 let (client_name, path) = self.json_path().unwrap();
                 println!(
@@ -94,7 +162,15 @@ impl<'a> ClientAdapter<'a> {
                     name,
                     path.display()
                 );
-                JsonManager::update_mcp_server(&path, client_name.as_str(), &name, cfg).await
+                let cfg = self.client_config(cfg);
+                JsonManager::update_mcp_server(
+                    &path,
+                    client_name.as_str(),
+                    &name,
+                    cfg,
+                    self.revision(),
+                )
+                .await
             }
         }
     }
@@ -109,6 +185,13 @@ impl<'a> ClientAdapter<'a> {
                 let servers = codex_cmds::read_mcp_servers().await?;
                 Ok(serde_json::json!({"mcpServers": servers}))
             }
+            ClientAdapter::Store(store) => {
+                println!("[Adapter][{}] batch delete servers", store.client());
+                for n in names {
+                    let _ = store.remove(&n).await;
+                }
+                Self::store_servers(store.as_ref()).await
+            }
             ClientAdapter::Json { .. } => {
                 let (client_name, path) = self.json_path().unwrap();
                 println!(
@@ -116,7 +199,13 @@ impl<'a> ClientAdapter<'a> {
                     client_name,
                     path.display()
                 );
-                JsonManager::batch_delete_mcp_servers(&path, client_name.as_str(), names).await
+                JsonManager::batch_delete_mcp_servers(
+                    &path,
+                    client_name.as_str(),
+                    names,
+                    self.revision(),
+                )
+                .await
             }
         }
     }
@@ -128,6 +217,10 @@ impl<'a> ClientAdapter<'a> {
                 println!("[Adapter][Codex] list disabled: {}", disabled.len());
                 Ok(serde_json::to_value(disabled).unwrap_or_default())
             }
+            ClientAdapter::Store(store) => {
+                println!("[Adapter][{}] list disabled", store.client());
+                Self::store_disabled(store.as_ref()).await
+            }
             ClientAdapter::Json { .. } => {
                 let (client_name, path) = self.json_path().unwrap();
                 println!(
@@ -148,6 +241,11 @@ impl<'a> ClientAdapter<'a> {
                 let disabled = codex_cmds::list_disabled().await?;
                 Ok(serde_json::to_value(disabled).unwrap_or_default())
             }
+            ClientAdapter::Store(store) => {
+                println!("[Adapter][{}] disable: {}", store.client(), name);
+                store.set_enabled(&name, false).await?;
+                Self::store_disabled(store.as_ref()).await
+            }
             ClientAdapter::Json { .. } => {
                 let (client_name, path) = self.json_path().unwrap();
                 println!(
@@ -156,7 +254,8 @@ impl<'a> ClientAdapter<'a> {
                     name,
                     path.display()
                 );
-                JsonManager::disable_mcp_server(&path, client_name.as_str(), &name).await
+                JsonManager::disable_mcp_server(&path, client_name.as_str(), &name, self.revision())
+                    .await
             }
         }
     }
@@ -169,6 +268,11 @@ impl<'a> ClientAdapter<'a> {
                 let disabled = codex_cmds::list_disabled().await?;
                 Ok(serde_json::to_value(disabled).unwrap_or_default())
             }
+            ClientAdapter::Store(store) => {
+                println!("[Adapter][{}] enable: {}", store.client(), name);
+                store.set_enabled(&name, true).await?;
+                Self::store_disabled(store.as_ref()).await
+            }
             ClientAdapter::Json { .. } => {
                 let (client_name, path) = self.json_path().unwrap();
                 println!(
@@ -177,7 +281,8 @@ impl<'a> ClientAdapter<'a> {
                     name,
                     path.display()
                 );
-                JsonManager::enable_mcp_server(&path, client_name.as_str(), &name).await
+                JsonManager::enable_mcp_server(&path, client_name.as_str(), &name, self.revision())
+                    .await
             }
         }
     }
@@ -192,6 +297,11 @@ impl<'a> ClientAdapter<'a> {
                 let disabled = codex_cmds::list_disabled().await?;
                 Ok(serde_json::to_value(disabled).unwrap_or_default())
             }
+            ClientAdapter::Store(store) => {
+                println!("[Adapter][{}] update disabled: {}", store.client(), name);
+                store.update_disabled(&name, cfg).await?;
+                Self::store_disabled(store.as_ref()).await
+            }
             ClientAdapter::Json { .. } => {
                 let (client_name, path) = self.json_path().unwrap();
                 println!(
@@ -200,8 +310,15 @@ impl<'a> ClientAdapter<'a> {
                     name,
                     path.display()
                 );
-                JsonManager::update_disabled_mcp_server(&path, client_name.as_str(), &name, cfg)
-                    .await
+                let cfg = self.client_config(cfg);
+                JsonManager::update_disabled_mcp_server(
+                    &path,
+                    client_name.as_str(),
+                    &name,
+                    cfg,
+                    self.revision(),
+                )
+                .await
             }
         }
     }
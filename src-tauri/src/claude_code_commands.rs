@@ -1,3 +1,4 @@
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -5,11 +6,15 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::command;
+use tokio::sync::Mutex;
 
 // ~/.claude.json {projects: { "working_dir": "mcpServers": server}, other_keys: {}}
 // {'sentry': {'type': 'http', 'url': 'https://mcp.sentry.dev/mcp'},
 //  'airtable': {'type': 'stdio', 'command': 'npx', 'args': ['-y', 'airtable-mcp-server'], 'env': {'AIRTABLE_API_KEY': 'YOUR_KEY'}}}
 
+// Keys mapped onto ClaudeCodeServer fields; anything else round-trips through `extra`
+const KNOWN_SERVER_KEYS: [&str; 7] = ["name", "type", "url", "command", "args", "env", "headers"];
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ClaudeCodeServer {
     pub name: String,
@@ -18,8 +23,15 @@ pub struct ClaudeCodeServer {
     pub command: Option<String>,
     pub args: Option<Vec<String>>,
     pub env: Option<HashMap<String, String>>,
+    pub headers: Option<HashMap<String, String>>,
+    /// Unknown keys (e.g. `oauth`) preserved so list/add/disable/enable/sync never drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
+/// Serializes read-modify-write cycles on ~/.claude.json
+pub(crate) static CLAUDE_CFG_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ClaudeCodeResponse {
     pub success: bool,
@@ -77,6 +89,7 @@ pub async fn claude_mcp_add(
     request: ClaudeCodeServer,
     working_dir: String,
 ) -> Result<ClaudeCodeResponse, String> {
+    let _guard = CLAUDE_CFG_LOCK.lock().await;
     let claude_config_path = get_claude_config_path(Some(working_dir.clone()))?;
 
     // Create backup if config file exists
@@ -116,10 +129,9 @@ pub async fn claude_mcp_add(
     config["projects"][&current_dir]["mcpServers"][&request.name] = server_json;
 
     // Write back to file
-    if let Err(e) = fs::write(
-        &claude_config_path,
-        serde_json::to_string_pretty(&config).unwrap(),
-    ) {
+    let content = serde_json::to_string_pretty(&config).unwrap();
+    crate::config_watcher::note_self_write(&claude_config_path, content.as_bytes());
+    if let Err(e) = fs::write(&claude_config_path, content) {
         // Restore backup if write fails
         if let Some(backup_path) = &backup_path {
             let _ = restore_backup(&claude_config_path, backup_path);
@@ -144,6 +156,7 @@ pub async fn claude_mcp_remove(
     name: String,
     working_dir: String,
 ) -> Result<ClaudeCodeResponse, String> {
+    let _guard = CLAUDE_CFG_LOCK.lock().await;
     let claude_config_path = get_claude_config_path(Some(working_dir.clone()))?;
 
     if !claude_config_path.exists() {
@@ -175,10 +188,9 @@ pub async fn claude_mcp_remove(
 
     if found {
         // Write back to file
-        if let Err(e) = fs::write(
-            &claude_config_path,
-            serde_json::to_string_pretty(&config).unwrap(),
-        ) {
+        let content = serde_json::to_string_pretty(&config).unwrap();
+        crate::config_watcher::note_self_write(&claude_config_path, content.as_bytes());
+        if let Err(e) = fs::write(&claude_config_path, content) {
             // Restore backup if write fails
             let _ = restore_backup(&claude_config_path, &backup_path);
             return Err(format!("Failed to write Claude config: {}", e));
@@ -245,12 +257,108 @@ pub fn check_claude_config_exists() -> Result<bool, String> {
     Ok(Path::new(&path).exists())
 }
 
-fn get_claude_config_path(_working_dir: Option<String>) -> Result<PathBuf, String> {
+pub(crate) fn get_claude_config_path(_working_dir: Option<String>) -> Result<PathBuf, String> {
     let home_dir = dirs::home_dir().ok_or("Unable to find home directory")?;
     Ok(home_dir.join(".claude.json"))
 }
 
-fn parse_server_config(name: &str, config: &serde_json::Value) -> Result<ClaudeCodeServer, String> {
+/// Read ~/.claude.json, returning an empty projects map when it does not exist
+pub(crate) fn read_claude_config(path: &Path) -> Result<serde_json::Value, String> {
+    if !path.exists() {
+        return Ok(serde_json::json!({"projects": {}}));
+    }
+
+    let config_content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read Claude config: {}", e))?;
+
+    serde_json::from_str(&config_content)
+        .map_err(|e| format!("Failed to parse Claude config: {}", e))
+}
+
+/// Get the `mcpServers` map of a project, creating missing levels on the way
+pub(crate) fn project_servers_mut<'a>(
+    config: &'a mut serde_json::Value,
+    working_dir: &str,
+) -> &'a mut serde_json::Map<String, serde_json::Value> {
+    if !config.is_object() {
+        *config = serde_json::json!({});
+    }
+    if !config["projects"].is_object() {
+        config["projects"] = serde_json::json!({});
+    }
+    if !config["projects"][working_dir].is_object() {
+        config["projects"][working_dir] = serde_json::json!({"mcpServers": {}});
+    }
+    if !config["projects"][working_dir]["mcpServers"].is_object() {
+        config["projects"][working_dir]["mcpServers"] = serde_json::json!({});
+    }
+    config["projects"][working_dir]["mcpServers"]
+        .as_object_mut()
+        .unwrap()
+}
+
+/// Write a file through a temp file and rename so readers never see a partial write
+pub(crate) fn write_file_atomic(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_path = path.with_file_name(format!("{}.tmp", file_name));
+
+    crate::config_watcher::note_self_write(path, content.as_bytes());
+    fs::write(&tmp_path, content)
+        .map_err(|e| format!("Failed to write temp file {}: {}", tmp_path.display(), e))?;
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to replace {}: {}", path.display(), e)
+    })
+}
+
+/// Set, or with `None` delete, a user-scope server (top-level `mcpServers`) in
+/// ~/.claude.json. Returns the new `{"mcpServers": ...}`.
+pub(crate) async fn set_user_server(
+    config_path: &Path,
+    name: &str,
+    server: Option<serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    let _guard = CLAUDE_CFG_LOCK.lock().await;
+    let mut config = read_claude_config(config_path)?;
+    let Some(root) = config.as_object_mut() else {
+        return Err("Claude config is not a JSON object".to_string());
+    };
+    let servers = root
+        .entry("mcpServers")
+        .or_insert_with(|| serde_json::json!({}));
+    if !servers.is_object() {
+        *servers = serde_json::json!({});
+    }
+    let servers = servers.as_object_mut().unwrap();
+    match server {
+        Some(server) => {
+            servers.insert(name.to_string(), server);
+        }
+        None => {
+            if servers.remove(name).is_none() {
+                return Err(format!("Server '{}' not found", name));
+            }
+        }
+    }
+    let result = serde_json::json!({"mcpServers": servers.clone()});
+
+    let content = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize Claude config: {}", e))?;
+    write_file_atomic(config_path, &content)?;
+    Ok(result)
+}
+
+pub(crate) fn parse_server_config(
+    name: &str,
+    config: &serde_json::Value,
+) -> Result<ClaudeCodeServer, String> {
     let server_type = config
         .get("type")
         .and_then(|v| v.as_str())
@@ -273,11 +381,18 @@ fn parse_server_config(name: &str, config: &serde_json::Value) -> Result<ClaudeC
             .collect()
     });
 
-    let env = config.get("env").and_then(|v| v.as_object()).map(|obj| {
-        obj.iter()
-            .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-            .collect()
-    });
+    let env = parse_string_map(config.get("env"));
+    let headers = parse_string_map(config.get("headers"));
+
+    let extra = config
+        .as_object()
+        .map(|obj| {
+            obj.iter()
+                .filter(|(k, _)| !KNOWN_SERVER_KEYS.contains(&k.as_str()))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
 
     Ok(ClaudeCodeServer {
         name: name.to_string(),
@@ -286,13 +401,30 @@ fn parse_server_config(name: &str, config: &serde_json::Value) -> Result<ClaudeC
         command,
         args,
         env,
+        headers,
+        extra,
     })
 }
 
-fn server_to_json(server: &ClaudeCodeServer) -> Result<serde_json::Value, String> {
-    let mut json = serde_json::json!({
-        "type": server.r#type
-    });
+fn parse_string_map(value: Option<&serde_json::Value>) -> Option<HashMap<String, String>> {
+    value.and_then(|v| v.as_object()).map(|obj| {
+        obj.iter()
+            .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+            .collect()
+    })
+}
+
+pub(crate) fn server_to_json(server: &ClaudeCodeServer) -> Result<serde_json::Value, String> {
+    // Start from the preserved unknown keys so known fields always win
+    let mut json = serde_json::Value::Object(
+        server
+            .extra
+            .iter()
+            .filter(|(k, _)| !KNOWN_SERVER_KEYS.contains(&k.as_str()))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    );
+    json["type"] = serde_json::Value::String(server.r#type.clone());
 
     if let Some(url) = &server.url {
         json["url"] = serde_json::Value::String(url.clone());
@@ -318,6 +450,15 @@ fn server_to_json(server: &ClaudeCodeServer) -> Result<serde_json::Value, String
         );
     }
 
+    if let Some(headers) = &server.headers {
+        json["headers"] = serde_json::Value::Object(
+            headers
+                .iter()
+                .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+                .collect(),
+        );
+    }
+
     Ok(json)
 }
 
@@ -0,0 +1,168 @@
+// Round-trip tests for Claude Code server conversion
+use crate::claude_code_commands::{
+    parse_server_config, server_to_json, set_user_server, ClaudeCodeServer,
+};
+use crate::claude_projects::{project_inventory, transfer_servers};
+use serde_json::json;
+
+#[test]
+fn test_headers_and_unknown_keys_round_trip() {
+    let config = json!({
+        "type": "http",
+        "url": "https://mcp.example.com/mcp",
+        "headers": {"Authorization": "Bearer token"},
+        "oauth": {"clientId": "abc"}
+    });
+    let server = parse_server_config("example", &config).unwrap();
+    assert_eq!(
+        server
+            .headers
+            .as_ref()
+            .unwrap()
+            .get("Authorization")
+            .unwrap(),
+        "Bearer token"
+    );
+    assert_eq!(server.extra["oauth"], json!({"clientId": "abc"}));
+    assert_eq!(server_to_json(&server).unwrap(), config);
+}
+
+#[test]
+fn test_known_fields_override_extra() {
+    let mut server = parse_server_config("example", &json!({"command": "npx"})).unwrap();
+    server.extra.insert("type".into(), json!("sse"));
+    let value = server_to_json(&server).unwrap();
+    assert_eq!(value["type"], json!("stdio"));
+}
+
+#[test]
+fn test_transfer_servers_to_glob_targets() {
+    let mut config = json!({
+        "projects": {
+            "/repo/main": {"mcpServers": {
+                "github": {"type": "http", "url": "https://gh"},
+                "sentry": {"type": "sse", "url": "https://sentry"}
+            }},
+            "/repo/wt-a": {"mcpServers": {"sentry": {"type": "sse", "url": "https://sentry-a"}}},
+            "/repo/wt-b": {"mcpServers": {
+                "github": {"type": "stdio", "command": "gh"},
+                "sentry": {"type": "sse", "url": "https://sentry-b"}
+            }},
+            "/other": {"mcpServers": {}}
+        }
+    });
+    let result = transfer_servers(
+        &mut config,
+        "/repo/main",
+        &["/repo/*".to_string()],
+        &["github".to_string(), "sentry".to_string()],
+        true,
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(result.targets, vec!["/repo/wt-a", "/repo/wt-b"]);
+    assert_eq!(result.copied, 1);
+    assert_eq!(result.skipped.len(), 3);
+    assert_eq!(result.removed, vec!["github"]);
+    // Skipped on every target, so it stays where it was
+    assert_eq!(result.not_moved, vec!["sentry"]);
+    assert_eq!(
+        config["projects"]["/repo/main"]["mcpServers"]["sentry"]["url"],
+        "https://sentry"
+    );
+    assert_eq!(
+        config["projects"]["/repo/wt-a"]["mcpServers"]["sentry"]["url"],
+        "https://sentry-a"
+    );
+    assert_eq!(
+        config["projects"]["/repo/wt-a"]["mcpServers"]["github"]["url"],
+        "https://gh"
+    );
+    assert_eq!(
+        config["projects"]["/repo/wt-b"]["mcpServers"]["github"]["command"],
+        "gh"
+    );
+    assert!(config["projects"]["/repo/main"]["mcpServers"]["github"].is_null());
+    assert!(config["projects"]["/other"]["mcpServers"]["github"].is_null());
+}
+
+#[test]
+fn test_project_inventory_lists_active_and_disabled_servers() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("app");
+    std::fs::create_dir(&project).unwrap();
+    std::fs::write(
+        project.join(".mcp.json"),
+        r#"{"mcpServers": {"shared": {"command": "npx", "args": ["shared-mcp"]}}}"#,
+    )
+    .unwrap();
+    let project = project.to_string_lossy().to_string();
+
+    let config = json!({
+        "projects": {
+            project.clone(): {"mcpServers": {
+                "github": {"type": "http", "url": "https://gh"},
+                "fetch": {"command": "uvx", "args": ["mcp-server-fetch"]}
+            }},
+            "/gone": {"mcpServers": {"old": {"command": "old"}}}
+        }
+    });
+    let disabled = json!({
+        "projects": {
+            project.clone(): {"sentry": {"type": "sse", "url": "https://sentry"}},
+            "/parked": {"db": {"command": "db-mcp"}}
+        }
+    });
+
+    let inventory = project_inventory(&config, &disabled);
+    let paths: Vec<&str> = inventory.iter().map(|p| p.path.as_str()).collect();
+    let mut expected = vec![project.as_str(), "/gone", "/parked"];
+    expected.sort();
+    assert_eq!(paths, expected);
+
+    let app = inventory.iter().find(|p| p.path == project).unwrap();
+    assert!(app.exists);
+    let names =
+        |servers: &[ClaudeCodeServer]| servers.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+    assert_eq!(names(&app.local_servers), vec!["fetch", "github"]);
+    assert_eq!(names(&app.disabled_servers), vec!["sentry"]);
+    assert_eq!(names(&app.mcp_json_servers), vec!["shared"]);
+    assert!(app.mcp_json_error.is_none());
+
+    let gone = inventory.iter().find(|p| p.path == "/gone").unwrap();
+    assert!(!gone.exists);
+    assert_eq!(names(&gone.local_servers), vec!["old"]);
+
+    // Only disabled servers left, but still part of the matrix
+    let parked = inventory.iter().find(|p| p.path == "/parked").unwrap();
+    assert!(parked.local_servers.is_empty());
+    assert_eq!(names(&parked.disabled_servers), vec!["db"]);
+}
+
+#[tokio::test]
+async fn test_set_user_server_keeps_the_rest_of_the_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join(".claude.json");
+    let original = json!({
+        "numStartups": 3,
+        "mcpServers": {"fetch": {"command": "uvx", "args": ["mcp-server-fetch"]}},
+        "projects": {"/repo": {"mcpServers": {"local": {"command": "./run.sh"}}}}
+    });
+    std::fs::write(&path, original.to_string()).unwrap();
+
+    let cfg = json!({"type": "http", "url": "https://mcp.sentry.dev/mcp"});
+    let servers = set_user_server(&path, "sentry", Some(cfg.clone()))
+        .await
+        .unwrap();
+    assert_eq!(servers["mcpServers"]["sentry"], cfg);
+    set_user_server(&path, "fetch", None).await.unwrap();
+    assert!(set_user_server(&path, "fetch", None).await.is_err());
+
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(written["mcpServers"], json!({"sentry": cfg}));
+    assert_eq!(written["numStartups"], 3);
+    assert_eq!(written["projects"], original["projects"]);
+    assert!(!temp_dir.path().join(".claude.json.tmp").exists());
+}
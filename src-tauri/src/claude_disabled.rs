@@ -1,15 +1,24 @@
+use crate::claude_code_commands::{
+    get_claude_config_path, parse_server_config, project_servers_mut, read_claude_config,
+    server_to_json, write_file_atomic, CLAUDE_CFG_LOCK,
+};
 use dirs::home_dir;
+use once_cell::sync::Lazy;
 use serde_json::{json, Value};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::command;
+use tokio::sync::Mutex;
+
+// Lock order when both files are touched: CLAUDE_CFG_LOCK first, then DISABLED_LOCK
+static DISABLED_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
 fn get_disabled_path() -> Result<PathBuf, String> {
     let home = home_dir().ok_or_else(|| "Failed to get home directory".to_string())?;
     Ok(home.join(".claude.disabled.json"))
 }
 
-fn read_disabled_file() -> Result<Value, String> {
+pub(crate) fn read_disabled_file() -> Result<Value, String> {
     let path = get_disabled_path()?;
     if !path.exists() {
         return Ok(json!({"projects": {}}));
@@ -22,15 +31,60 @@ fn read_disabled_file() -> Result<Value, String> {
 
 fn write_disabled_file(v: &Value) -> Result<(), String> {
     let path = get_disabled_path()?;
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("Create dir failed: {}", e))?;
-    }
-    fs::write(&path, serde_json::to_string_pretty(v).unwrap())
+    write_file_atomic(&path, &serde_json::to_string_pretty(v).unwrap())
         .map_err(|e| format!("Write disabled file: {}", e))
 }
 
+fn ensure_disabled_project(disabled: &mut Value, working_dir: &str) {
+    if !disabled["projects"].is_object() {
+        disabled["projects"] = json!({});
+    }
+    if !disabled["projects"][working_dir].is_object() {
+        disabled["projects"][working_dir] = json!({});
+    }
+}
+
+/// Write `first` then `second`. If `second` fails, `first` is put back to its previous
+/// content, so callers should stage the side that adds the server as `first`.
+fn commit_pair(first: (&Path, &Value), second: (&Path, &Value)) -> Result<(), String> {
+    let (first_path, first_value) = first;
+    let (second_path, second_value) = second;
+
+    let previous = if first_path.exists() {
+        Some(
+            fs::read_to_string(first_path)
+                .map_err(|e| format!("Failed to read {}: {}", first_path.display(), e))?,
+        )
+    } else {
+        None
+    };
+
+    write_file_atomic(
+        first_path,
+        &serde_json::to_string_pretty(first_value).unwrap(),
+    )?;
+
+    if let Err(e) = write_file_atomic(
+        second_path,
+        &serde_json::to_string_pretty(second_value).unwrap(),
+    ) {
+        let rollback = match &previous {
+            Some(content) => write_file_atomic(first_path, content),
+            None => fs::remove_file(first_path)
+                .map_err(|e| format!("Failed to remove {}: {}", first_path.display(), e)),
+        };
+        return match rollback {
+            Ok(()) => Err(e),
+            Err(rollback_err) => Err(format!("{}; rollback failed: {}", e, rollback_err)),
+        };
+    }
+
+    Ok(())
+}
+
 #[command]
 pub async fn claude_list_disabled(working_dir: String) -> Result<Value, String> {
+    let _guard = DISABLED_LOCK.lock().await;
     let v = read_disabled_file()?;
     Ok(v.get("projects")
         .and_then(|p| p.get(&working_dir))
@@ -40,91 +94,73 @@ pub async fn claude_list_disabled(working_dir: String) -> Result<Value, String>
 
 #[command]
 pub async fn claude_disable_server(working_dir: String, name: String) -> Result<Value, String> {
-    // Read current disabled and Claude config to fetch config for the named server
+    let _cfg_guard = CLAUDE_CFG_LOCK.lock().await;
+    let _disabled_guard = DISABLED_LOCK.lock().await;
+
+    let claude_path = get_claude_config_path(None)?;
+    let disabled_path = get_disabled_path()?;
+
+    // Stage removal from ~/.claude.json active list
+    let mut config = read_claude_config(&claude_path)?;
+    let raw = config
+        .get_mut("projects")
+        .and_then(|p| p.get_mut(&working_dir))
+        .and_then(|p| p.get_mut("mcpServers"))
+        .and_then(|m| m.as_object_mut())
+        .and_then(|m| m.remove(&name))
+        .ok_or_else(|| format!("Server '{}' not found in active servers", name))?;
+
+    // Convert to JSON matching Manage shape, keeping headers and unknown keys
+    let server = parse_server_config(&name, &raw)?;
+    let cfg = server_to_json(&server)?;
+
+    // Stage insertion into the disabled store
     let mut disabled = read_disabled_file()?;
-    if !disabled["projects"].is_object() {
-        disabled["projects"] = json!({});
-    }
-    if !disabled["projects"][&working_dir].is_object() {
-        disabled["projects"][&working_dir] = json!({});
-    }
+    ensure_disabled_project(&mut disabled, &working_dir);
+    disabled["projects"][&working_dir][&name] = cfg;
+
+    // Disabled store first: a failure part-way leaves the server in both files, never in neither
+    commit_pair((&disabled_path, &disabled), (&claude_path, &config))?;
 
-    // Try to read from Claude config to copy server config
-    let servers = crate::claude_code_commands::claude_mcp_list(working_dir.clone()).await?;
-    if let Some(s) = servers.into_iter().find(|s| s.name == name) {
-        // Convert to JSON matching Manage shape
-        let mut cfg = json!({"type": s.r#type});
-        if let Some(url) = s.url {
-            cfg["url"] = json!(url);
-        }
-        if let Some(cmd) = s.command {
-            cfg["command"] = json!(cmd);
-        }
-        if let Some(args) = s.args {
-            cfg["args"] = json!(args);
-        }
-        if let Some(env) = s.env {
-            cfg["env"] = json!(env);
-        }
-        disabled["projects"][&working_dir][&name] = cfg;
-        write_disabled_file(&disabled)?;
-
-        // Remove from ~/.claude.json active list
-        let _ =
-            crate::claude_code_commands::claude_mcp_remove(name.clone(), working_dir.clone()).await;
-    }
     Ok(disabled["projects"][&working_dir].clone())
 }
 
 #[command]
 pub async fn claude_enable_server(working_dir: String, name: String) -> Result<Value, String> {
+    let _cfg_guard = CLAUDE_CFG_LOCK.lock().await;
+    let _disabled_guard = DISABLED_LOCK.lock().await;
+
+    let claude_path = get_claude_config_path(None)?;
+    let disabled_path = get_disabled_path()?;
+
+    // Stage removal from the disabled store
     let mut disabled = read_disabled_file()?;
+    let cfg = disabled
+        .get_mut("projects")
+        .and_then(|p| p.get_mut(&working_dir))
+        .and_then(|m| m.as_object_mut())
+        .and_then(|m| m.remove(&name))
+        .ok_or_else(|| format!("Server '{}' not found in disabled servers", name))?;
 
-    // Read config from disabled store to re-add
-    let maybe_cfg = disabled
-        .get("projects")
-        .and_then(|p| p.get(&working_dir))
-        .and_then(|m| m.get(&name))
-        .cloned();
-
-    if let Some(cfg) = maybe_cfg {
-        // Map disabled config back to ClaudeCodeServer and add
-        let server = crate::claude_code_commands::ClaudeCodeServer {
-            name: name.clone(),
-            r#type: cfg
-                .get("type")
-                .and_then(|v| v.as_str())
-                .unwrap_or("http")
-                .to_string(),
-            url: cfg
-                .get("url")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            command: cfg
-                .get("command")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            args: cfg.get("args").and_then(|v| v.as_array()).map(|arr| {
-                arr.iter()
-                    .filter_map(|x| x.as_str().map(|s| s.to_string()))
-                    .collect()
-            }),
-            env: cfg.get("env").and_then(|v| v.as_object()).map(|m| {
-                m.iter()
-                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-                    .collect()
-            }),
-        };
-        let _ = crate::claude_code_commands::claude_mcp_add(server, working_dir.clone()).await;
+    // Map disabled config back to ClaudeCodeServer
+    let mut server = parse_server_config(&name, &cfg)?;
+    if cfg.get("type").is_none() && cfg.get("command").is_none() {
+        server.r#type = "http".to_string();
     }
 
-    // Remove from disabled store
-    if disabled["projects"].is_object() && disabled["projects"][&working_dir].is_object() {
-        if let Some(map) = disabled["projects"][&working_dir].as_object_mut() {
-            map.remove(&name);
-        }
+    // Stage insertion into ~/.claude.json
+    let mut config = read_claude_config(&claude_path)?;
+    let servers = project_servers_mut(&mut config, &working_dir);
+    if servers.contains_key(&name) {
+        return Err(format!(
+            "Server '{}' already exists in active servers",
+            name
+        ));
     }
-    write_disabled_file(&disabled)?;
+    servers.insert(name.clone(), server_to_json(&server)?);
+
+    // Claude config first: a failure part-way leaves the server in both files, never in neither
+    commit_pair((&claude_path, &config), (&disabled_path, &disabled))?;
 
     Ok(disabled
         .get("projects")
@@ -139,13 +175,9 @@ pub async fn claude_update_disabled(
     name: String,
     server_config: Value,
 ) -> Result<Value, String> {
+    let _guard = DISABLED_LOCK.lock().await;
     let mut disabled = read_disabled_file()?;
-    if !disabled["projects"].is_object() {
-        disabled["projects"] = json!({});
-    }
-    if !disabled["projects"][&working_dir].is_object() {
-        disabled["projects"][&working_dir] = json!({});
-    }
+    ensure_disabled_project(&mut disabled, &working_dir);
     disabled["projects"][&working_dir][&name] = server_config;
     write_disabled_file(&disabled)?;
     Ok(disabled["projects"][&working_dir].clone())
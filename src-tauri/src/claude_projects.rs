@@ -0,0 +1,268 @@
+use crate::claude_code_commands::{
+    get_claude_config_path, parse_server_config, project_servers_mut, read_claude_config,
+    write_file_atomic, ClaudeCodeServer, CLAUDE_CFG_LOCK,
+};
+use crate::claude_disabled::read_disabled_file;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use tauri::command;
+
+const GLOB_CHARS: [char; 4] = ['*', '?', '[', '{'];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClaudeProjectInventory {
+    pub path: String,
+    /// False when the project directory no longer exists on disk
+    pub exists: bool,
+    /// Local-scope servers from `projects.<path>.mcpServers` in ~/.claude.json
+    pub local_servers: Vec<ClaudeCodeServer>,
+    /// Servers parked in ~/.claude.disabled.json for this project
+    pub disabled_servers: Vec<ClaudeCodeServer>,
+    /// Project-scope servers from `<path>/.mcp.json`
+    pub mcp_json_servers: Vec<ClaudeCodeServer>,
+    pub mcp_json_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClaudeTransferSkip {
+    pub project: String,
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ClaudeTransferResult {
+    /// Project paths the servers were written to
+    pub targets: Vec<String>,
+    pub copied: usize,
+    pub skipped: Vec<ClaudeTransferSkip>,
+    /// Names removed from the source project (move only)
+    pub removed: Vec<String>,
+    /// Names left in the source because every target skipped them (move only)
+    pub not_moved: Vec<String>,
+}
+
+fn servers_from_map(map: Option<&Value>) -> Vec<ClaudeCodeServer> {
+    let mut servers: Vec<ClaudeCodeServer> = map
+        .and_then(|m| m.as_object())
+        .map(|obj| {
+            obj.iter()
+                .filter_map(|(name, cfg)| parse_server_config(name, cfg).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    servers.sort_by(|a, b| a.name.cmp(&b.name));
+    servers
+}
+
+fn read_mcp_json(project_dir: &Path) -> (Vec<ClaudeCodeServer>, Option<String>) {
+    let path = project_dir.join(".mcp.json");
+    if !path.exists() {
+        return (Vec::new(), None);
+    }
+    let parsed = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+        .and_then(|content| {
+            serde_json::from_str::<Value>(&content)
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+        });
+    match parsed {
+        Ok(json) => (servers_from_map(json.get("mcpServers")), None),
+        Err(e) => (Vec::new(), Some(e)),
+    }
+}
+
+/// Inventory of every project in `config` (~/.claude.json) and `disabled`
+/// (~/.claude.disabled.json)
+pub(crate) fn project_inventory(config: &Value, disabled: &Value) -> Vec<ClaudeProjectInventory> {
+    let mut paths: Vec<String> = config
+        .get("projects")
+        .and_then(|p| p.as_object())
+        .map(|obj| obj.keys().cloned().collect())
+        .unwrap_or_default();
+    // Projects that only have disabled servers left still belong in the matrix
+    if let Some(obj) = disabled.get("projects").and_then(|p| p.as_object()) {
+        for key in obj.keys() {
+            if !paths.contains(key) {
+                paths.push(key.clone());
+            }
+        }
+    }
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let dir = Path::new(&path);
+            let exists = dir.is_dir();
+            let local_servers = servers_from_map(
+                config
+                    .get("projects")
+                    .and_then(|p| p.get(&path))
+                    .and_then(|p| p.get("mcpServers")),
+            );
+            let disabled_servers =
+                servers_from_map(disabled.get("projects").and_then(|p| p.get(&path)));
+            let (mcp_json_servers, mcp_json_error) = if exists {
+                read_mcp_json(dir)
+            } else {
+                (Vec::new(), None)
+            };
+            ClaudeProjectInventory {
+                path,
+                exists,
+                local_servers,
+                disabled_servers,
+                mcp_json_servers,
+                mcp_json_error,
+            }
+        })
+        .collect()
+}
+
+/// List every Claude Code project with its local, disabled and `.mcp.json` servers
+#[command]
+pub async fn claude_project_inventory() -> Result<Vec<ClaudeProjectInventory>, String> {
+    let config = read_claude_config(&get_claude_config_path(None)?)?;
+    let disabled = read_disabled_file()?;
+    Ok(project_inventory(&config, &disabled))
+}
+
+/// Resolve target patterns against the project keys of ~/.claude.json.
+/// Plain paths that are not yet projects are kept so a server can seed a new project.
+fn resolve_targets(
+    config: &Value,
+    from_working_dir: &str,
+    patterns: &[String],
+) -> Result<Vec<String>, String> {
+    let projects: Vec<String> = config
+        .get("projects")
+        .and_then(|p| p.as_object())
+        .map(|obj| obj.keys().cloned().collect())
+        .unwrap_or_default();
+
+    let mut targets = Vec::new();
+    for pattern in patterns {
+        if pattern.contains(GLOB_CHARS) {
+            let matcher = glob::Pattern::new(pattern)
+                .map_err(|e| format!("Invalid project pattern '{}': {}", pattern, e))?;
+            for project in projects.iter().filter(|p| matcher.matches(p)) {
+                if !targets.contains(project) {
+                    targets.push(project.clone());
+                }
+            }
+        } else if !targets.contains(pattern) {
+            targets.push(pattern.clone());
+        }
+    }
+    targets.retain(|t| t != from_working_dir);
+    targets.sort();
+    Ok(targets)
+}
+
+/// Copy (or move) `names` from one project to every project matching `target_patterns`
+pub(crate) fn transfer_servers(
+    config: &mut Value,
+    from_working_dir: &str,
+    target_patterns: &[String],
+    names: &[String],
+    move_servers: bool,
+    overwrite: bool,
+) -> Result<ClaudeTransferResult, String> {
+    let source = config
+        .get("projects")
+        .and_then(|p| p.get(from_working_dir))
+        .and_then(|p| p.get("mcpServers"))
+        .and_then(|m| m.as_object())
+        .cloned()
+        .ok_or_else(|| format!("Project '{}' has no MCP servers", from_working_dir))?;
+
+    let mut selected = Vec::new();
+    for name in names {
+        let cfg = source
+            .get(name)
+            .ok_or_else(|| format!("Server '{}' not found in '{}'", name, from_working_dir))?;
+        selected.push((name.clone(), cfg.clone()));
+    }
+
+    let targets = resolve_targets(config, from_working_dir, target_patterns)?;
+    if targets.is_empty() {
+        return Err("No target projects matched".to_string());
+    }
+
+    let mut result = ClaudeTransferResult {
+        targets: targets.clone(),
+        ..Default::default()
+    };
+    let mut copied_names = HashSet::new();
+    for target in &targets {
+        let servers = project_servers_mut(config, target);
+        for (name, cfg) in &selected {
+            if servers.contains_key(name) && !overwrite {
+                result.skipped.push(ClaudeTransferSkip {
+                    project: target.clone(),
+                    name: name.clone(),
+                    reason: "already exists".to_string(),
+                });
+                continue;
+            }
+            servers.insert(name.clone(), cfg.clone());
+            copied_names.insert(name.clone());
+            result.copied += 1;
+        }
+    }
+
+    if move_servers {
+        let source_servers = project_servers_mut(config, from_working_dir);
+        for (name, _) in &selected {
+            if copied_names.contains(name) {
+                source_servers.remove(name);
+                result.removed.push(name.clone());
+            } else {
+                result.not_moved.push(name.clone());
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Copy or move servers between Claude Code projects with a single rewrite of ~/.claude.json
+#[command]
+pub async fn claude_transfer_servers(
+    from_working_dir: String,
+    target_patterns: Vec<String>,
+    names: Vec<String>,
+    move_servers: bool,
+    overwrite: bool,
+) -> Result<ClaudeTransferResult, String> {
+    if names.is_empty() {
+        return Err("No servers selected".to_string());
+    }
+
+    let _guard = CLAUDE_CFG_LOCK.lock().await;
+    let claude_path = get_claude_config_path(None)?;
+    if !claude_path.exists() {
+        return Err("Claude config file not found".to_string());
+    }
+
+    let mut config = read_claude_config(&claude_path)?;
+    let result = transfer_servers(
+        &mut config,
+        &from_working_dir,
+        &target_patterns,
+        &names,
+        move_servers,
+        overwrite,
+    )?;
+
+    write_file_atomic(
+        &claude_path,
+        &serde_json::to_string_pretty(&config).unwrap(),
+    )?;
+
+    Ok(result)
+}
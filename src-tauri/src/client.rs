@@ -1,6 +1,27 @@
 use dirs::home_dir;
+use serde_json::Value;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+/// JSON clients resolved by `ClientConfig::new`, in the order discovery reports them
+pub const KNOWN_CLIENTS: [&str; 9] = [
+    "claude",
+    "cline",
+    "roo_code",
+    "copilot",
+    "cursor",
+    "windsurf",
+    "gemini",
+    "mcphub",
+    "mcplinker",
+];
+
+/// VS Code builds that keep settings and extension storage under their own product dir
+const VSCODE_PRODUCT_DIRS: [&str; 3] = ["Code", "Code - Insiders", "VSCodium"];
+
+/// Product dirs that can host VS Code extensions such as Cline and Roo Code
+const VSCODE_EXTENSION_HOSTS: [&str; 4] = ["Code", "Code - Insiders", "VSCodium", "Cursor"];
+
 pub struct ClientConfig {
     pub path: PathBuf,
 }
@@ -8,8 +29,15 @@ pub struct ClientConfig {
 impl ClientConfig {
     pub fn new(name: &str, path: Option<&str>) -> Self {
         let home = home_dir().expect("Failed to get home directory");
+        if let Some(def) = crate::client_defs::definition(name) {
+            return Self {
+                path: def.resolve_path(&home, path),
+            };
+        }
 
         let path = match (name, path) {
+            // A config file found by discovery or picked by the user is used as-is
+            (_, Some(file)) if file.ends_with(".json") => PathBuf::from(file),
             ("claude", _) => Self::claude_config_path(&home),
             ("cline", _) => Self::cline_config_path(&home),
             ("roo_code", Some(base_path)) if !base_path.is_empty() => {
@@ -19,13 +47,32 @@ impl ClientConfig {
             ("copilot", Some(base_path)) if !base_path.is_empty() => {
                 PathBuf::from(base_path).join(".vscode/mcp.json")
             }
-            ("copilot", _) => home.join(".vscode/mcp.json"),
+            ("copilot", _) => Self::vscode_user_dir(&home, "Code")
+                .map(|user_dir| user_dir.join("mcp.json"))
+                .unwrap_or_default(),
             ("cursor", Some(base_path)) if !base_path.is_empty() => {
                 PathBuf::from(base_path).join(".cursor/mcp.json")
             }
             ("cursor", _) => home.join(".cursor/mcp.json"),
+            ("gemini", Some(base_path)) if !base_path.is_empty() => {
+                PathBuf::from(base_path).join(".gemini/settings.json")
+            }
+            ("gemini", _) => home.join(".gemini/settings.json"),
             ("mcphub", _) => home.join(".config/mcphub/servers.json"),
             ("windsurf", _) => home.join(".codeium/windsurf/mcp_config.json"),
+            ("zed", Some(base_path)) if !base_path.is_empty() => {
+                PathBuf::from(base_path).join(".zed/settings.json")
+            }
+            ("zed", _) => Self::zed_config_path(&home),
+            ("goose", Some(path_str)) if !path_str.is_empty() => PathBuf::from(path_str),
+            ("goose", _) => Self::goose_config_path(&home),
+            ("continue", Some(base_path)) if base_path.ends_with(".continue") => {
+                PathBuf::from(base_path)
+            }
+            ("continue", Some(base_path)) if !base_path.is_empty() => {
+                PathBuf::from(base_path).join(".continue")
+            }
+            ("continue", _) => home.join(".continue"),
 ("mcplinker", _) => home.join(".config/mcplinker/mcp.json"),
             (_, Some(path_str)) if !path_str.is_empty() => {
                 let given_path = PathBuf::from(path_str);
@@ -42,6 +89,159 @@ impl ClientConfig {
         Self { path }
     }
 
+    /// Every global location a client may use, labelled with the variant it belongs to.
+    /// `new` picks the primary one; discovery checks them all.
+    pub fn known_locations(name: &str) -> Vec<(Option<String>, PathBuf)> {
+        let home = match home_dir() {
+            Some(home) => home,
+            None => return Vec::new(),
+        };
+
+        match name {
+            "claude" => {
+                if cfg!(target_os = "linux") {
+                    Self::xdg_config_dirs(&home)
+                        .into_iter()
+                        .map(|dir| (None, dir.join("Claude/claude_desktop_config.json")))
+                        .collect()
+                } else {
+                    vec![(None, Self::claude_config_path(&home))]
+                }
+            }
+            "copilot" => VSCODE_PRODUCT_DIRS
+                .iter()
+                .filter_map(|product| {
+                    Self::vscode_user_dir(&home, product).map(|user_dir| (product, user_dir))
+                })
+                .flat_map(|(product, user_dir)| {
+                    let mut paths = vec![(Some(product.to_string()), user_dir.join("mcp.json"))];
+                    paths.extend(Self::vscode_profiles(&user_dir).into_iter().map(
+                        |(profile, dir)| {
+                            (
+                                Some(format!("{} ({})", product, profile)),
+                                dir.join("mcp.json"),
+                            )
+                        },
+                    ));
+                    paths
+                })
+                .collect(),
+            "cline" => Self::vscode_variant_paths(
+                &home,
+                "saoudrizwan.claude-dev",
+                "cline_mcp_settings.json",
+            ),
+            "roo_code" => {
+                Self::vscode_variant_paths(&home, "rooveterinaryinc.roo-cline", "mcp_settings.json")
+            }
+            "mcphub" | "mcplinker" | "zed" | "goose" if cfg!(target_os = "linux") => {
+                let relative = match name {
+                    "mcphub" => "mcphub/servers.json",
+                    "zed" => "zed/settings.json",
+                    "goose" => "goose/config.yaml",
+                    _ => "mcplinker/mcp.json",
+                };
+                Self::xdg_config_dirs(&home)
+                    .into_iter()
+                    .map(|dir| (None, dir.join(relative)))
+                    .collect()
+            }
+            _ => {
+                let path = Self::new(name, None).path;
+                if path.as_os_str().is_empty() {
+                    Vec::new()
+                } else {
+                    vec![(None, path)]
+                }
+            }
+        }
+    }
+
+    /// `~/.config` plus `$XDG_CONFIG_HOME` when it points somewhere else
+    fn xdg_config_dirs(home: &Path) -> Vec<PathBuf> {
+        let mut dirs = vec![home.join(".config")];
+        if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|xdg| xdg.is_absolute() && !dirs.contains(xdg))
+        {
+            dirs.push(xdg);
+        }
+        dirs
+    }
+
+    fn vscode_variant_paths(
+        home: &Path,
+        extension_id: &str,
+        filename: &str,
+    ) -> Vec<(Option<String>, PathBuf)> {
+        VSCODE_EXTENSION_HOSTS
+            .iter()
+            .filter_map(|product| {
+                Self::vscode_user_dir(home, product).map(|user_dir| {
+                    (
+                        Some(product.to_string()),
+                        user_dir
+                            .join("globalStorage")
+                            .join(extension_id)
+                            .join("settings")
+                            .join(filename),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn vscode_user_dir(home: &Path, product: &str) -> Option<PathBuf> {
+        let base = if cfg!(target_os = "macos") {
+            home.join("Library/Application Support")
+        } else if cfg!(target_os = "windows") {
+            home.join("AppData/Roaming")
+        } else if cfg!(target_os = "linux") {
+            home.join(".config")
+        } else {
+            return None;
+        };
+        Some(base.join(product).join("User"))
+    }
+
+    /// Named profiles of a VS Code user dir, as (profile name, profile dir).
+    /// Names come from `globalStorage/storage.json`; unlisted dirs use their id.
+    fn vscode_profiles(user_dir: &Path) -> Vec<(String, PathBuf)> {
+        let profiles_dir = user_dir.join("profiles");
+        let entries = match std::fs::read_dir(&profiles_dir) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        let names: HashMap<String, String> =
+            std::fs::read_to_string(user_dir.join("globalStorage/storage.json"))
+                .ok()
+                .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+                .and_then(|storage| storage.get("userDataProfiles").cloned())
+                .and_then(|profiles| profiles.as_array().cloned())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|profile| {
+                    Some((
+                        profile.get("location")?.as_str()?.to_string(),
+                        profile.get("name")?.as_str()?.to_string(),
+                    ))
+                })
+                .collect();
+
+        let mut profiles: Vec<(String, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| {
+                let id = entry.file_name().to_string_lossy().to_string();
+                let name = names.get(&id).cloned().unwrap_or(id);
+                (name, entry.path())
+            })
+            .collect();
+        profiles.sort();
+        profiles
+    }
+
     fn claude_config_path(home: &Path) -> PathBuf {
         if cfg!(target_os = "macos") {
             home.join("Library/Application Support/Claude/claude_desktop_config.json")
@@ -52,18 +252,31 @@ impl ClientConfig {
         }
     }
 
-    fn vscode_global_storage_path(home: &Path, extension_id: &str, filename: &str) -> PathBuf {
-        let base_path = if cfg!(target_os = "macos") {
-            home.join("Library/Application Support/Code/User/globalStorage")
-        } else if cfg!(target_os = "windows") {
-            home.join("AppData/Roaming/Code/User/globalStorage")
-        } else if cfg!(target_os = "linux") {
-            home.join(".config/Code/User/globalStorage")
+    fn zed_config_path(home: &Path) -> PathBuf {
+        if cfg!(target_os = "windows") {
+            home.join("AppData/Roaming/Zed/settings.json")
         } else {
-            return PathBuf::new();
-        };
+            home.join(".config/zed/settings.json")
+        }
+    }
+
+    fn goose_config_path(home: &Path) -> PathBuf {
+        if cfg!(target_os = "windows") {
+            home.join("AppData/Roaming/Block/goose/config/config.yaml")
+        } else {
+            home.join(".config/goose/config.yaml")
+        }
+    }
 
-        base_path.join(extension_id).join("settings").join(filename)
+    /// The first VS Code build that already has the extension's settings, else stable Code
+    fn vscode_global_storage_path(home: &Path, extension_id: &str, filename: &str) -> PathBuf {
+        let paths = Self::vscode_variant_paths(home, extension_id, filename);
+        paths
+            .iter()
+            .find(|(_, path)| path.is_file())
+            .or_else(|| paths.first())
+            .map(|(_, path)| path.clone())
+            .unwrap_or_default()
     }
 
     fn cline_config_path(home: &Path) -> PathBuf {
@@ -0,0 +1,556 @@
+use crate::client::ClientConfig;
+use crate::json_manager::JsonManager;
+use crate::store::ServerStore;
+use async_trait::async_trait;
+use dirs::home_dir;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+// Clients whose config is `mcpServers`-like JSON under other names are described by a
+// definition file instead of code. Bundled definitions live in `src-tauri/clients/`;
+// users can add or override one with `~/.config/mcplinker/clients/<id>.json`.
+
+const BUNDLED_DEFINITIONS: [&str; 2] = [
+    include_str!("../clients/opencode.json"),
+    include_str!("../clients/lmstudio.json"),
+];
+
+/// Common fields that a definition may rename
+const MAPPED_FIELDS: [&str; 5] = ["command", "args", "env", "url", "headers"];
+
+static REGISTRY: Lazy<HashMap<String, ClientDefinition>> = Lazy::new(load_registry);
+
+static DEFINED_CFG_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientDefinition {
+    pub id: String,
+    pub label: String,
+    pub paths: DefinitionPaths,
+    /// Key of the server map in the config file
+    #[serde(default = "default_root_key")]
+    pub root_key: String,
+    /// Common field name -> client field name
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// `command` holds the executable and its arguments as one array
+    #[serde(default)]
+    pub command_array: bool,
+    /// Field carrying the transport; `null` when the client infers it
+    #[serde(default = "default_type_field")]
+    pub type_field: Option<String>,
+    /// Common type (stdio, http, sse) -> client type value
+    #[serde(default)]
+    pub types: BTreeMap<String, String>,
+    #[serde(default)]
+    pub disable: DisableStrategy,
+}
+
+/// Path templates; `~` is the home dir, `project` is relative to the base path
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DefinitionPaths {
+    pub linux: Option<String>,
+    pub macos: Option<String>,
+    pub windows: Option<String>,
+    pub project: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "camelCase")]
+pub enum DisableStrategy {
+    /// Disabled servers move to a separate map at the document root
+    Section {
+        #[serde(default = "default_disabled_key")]
+        key: String,
+    },
+    /// Disabled servers stay in place with `field` set to `disabled_value`
+    #[serde(rename_all = "camelCase")]
+    Field {
+        field: String,
+        disabled_value: Value,
+        /// Written when enabling; the field is removed when unset
+        enabled_value: Option<Value>,
+    },
+}
+
+impl Default for DisableStrategy {
+    fn default() -> Self {
+        DisableStrategy::Section {
+            key: default_disabled_key(),
+        }
+    }
+}
+
+fn default_root_key() -> String {
+    "mcpServers".to_string()
+}
+
+fn default_type_field() -> Option<String> {
+    Some("type".to_string())
+}
+
+fn default_disabled_key() -> String {
+    "__disabled".to_string()
+}
+
+fn parse_definition(content: &str) -> Result<ClientDefinition, String> {
+    let def: ClientDefinition =
+        serde_json::from_str(content).map_err(|e| format!("Invalid client definition: {}", e))?;
+    if def.id.is_empty() {
+        return Err("Client definition has an empty `id`".to_string());
+    }
+    Ok(def)
+}
+
+fn user_definitions_dir() -> Option<PathBuf> {
+    home_dir().map(|home| home.join(".config/mcplinker/clients"))
+}
+
+fn load_registry() -> HashMap<String, ClientDefinition> {
+    let mut registry = HashMap::new();
+    for content in BUNDLED_DEFINITIONS {
+        match parse_definition(content) {
+            Ok(def) => {
+                registry.insert(def.id.clone(), def);
+            }
+            Err(e) => println!("[ClientDefs] bundled definition skipped: {}", e),
+        }
+    }
+
+    let entries = user_definitions_dir().and_then(|dir| std::fs::read_dir(dir).ok());
+    for entry in entries.into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| parse_definition(&content));
+        match parsed {
+            Ok(def) => {
+                println!("[ClientDefs] loaded {} from {}", def.id, path.display());
+                registry.insert(def.id.clone(), def);
+            }
+            Err(e) => println!("[ClientDefs] {} skipped: {}", path.display(), e),
+        }
+    }
+    registry
+}
+
+/// Load bundled and user definitions; called once at startup so errors show up early
+pub fn init_registry() {
+    println!("[ClientDefs] {} client definitions", REGISTRY.len());
+}
+
+pub fn definition(client: &str) -> Option<&'static ClientDefinition> {
+    REGISTRY.get(client)
+}
+
+pub fn defined_clients() -> Vec<&'static str> {
+    let mut ids: Vec<&str> = REGISTRY.keys().map(|id| id.as_str()).collect();
+    ids.sort();
+    ids
+}
+
+#[tauri::command]
+pub fn list_client_definitions() -> Vec<ClientDefinition> {
+    defined_clients()
+        .into_iter()
+        .filter_map(definition)
+        .cloned()
+        .collect()
+}
+
+impl ClientDefinition {
+    /// Config file for `path`: a `.json` file is used as-is, anything else is a project
+    /// base path, and no path means the global file for this OS.
+    pub fn resolve_path(&self, home: &Path, path: Option<&str>) -> PathBuf {
+        match path {
+            Some(file) if file.ends_with(".json") => PathBuf::from(file),
+            Some(base) if !base.is_empty() => match &self.paths.project {
+                Some(project) => Path::new(base).join(project),
+                None => self.global_path(home),
+            },
+            _ => self.global_path(home),
+        }
+    }
+
+    fn global_path(&self, home: &Path) -> PathBuf {
+        let template = if cfg!(target_os = "macos") {
+            &self.paths.macos
+        } else if cfg!(target_os = "windows") {
+            &self.paths.windows
+        } else {
+            &self.paths.linux
+        };
+        match template.as_deref() {
+            Some(template) => match template.strip_prefix("~/") {
+                Some(rest) => home.join(rest),
+                None => PathBuf::from(template),
+            },
+            None => PathBuf::new(),
+        }
+    }
+
+    fn field<'a>(&'a self, common: &'a str) -> &'a str {
+        self.fields
+            .get(common)
+            .map(|f| f.as_str())
+            .unwrap_or(common)
+    }
+
+    /// Keys of an entry that this definition maps; everything else passes through
+    fn managed_keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = MAPPED_FIELDS.iter().map(|f| self.field(f)).collect();
+        if let Some(type_field) = &self.type_field {
+            keys.push(type_field);
+        }
+        if let DisableStrategy::Field { field, .. } = &self.disable {
+            keys.push(field);
+        }
+        keys
+    }
+
+    /// Convert a client entry into the common shape
+    pub fn to_common(&self, entry: &Value) -> Value {
+        let Some(obj) = entry.as_object() else {
+            return entry.clone();
+        };
+        let managed = self.managed_keys();
+        let mut out: Map<String, Value> = obj
+            .iter()
+            .filter(|(key, _)| !managed.contains(&key.as_str()))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        for common in MAPPED_FIELDS {
+            if let Some(value) = obj.get(self.field(common)) {
+                out.insert(common.to_string(), value.clone());
+            }
+        }
+
+        if self.command_array {
+            let parts = out.get("command").and_then(|c| c.as_array()).cloned();
+            if let Some(mut parts) = parts {
+                if parts.is_empty() {
+                    out.remove("command");
+                } else {
+                    out.insert("command".into(), parts.remove(0));
+                    out.insert("args".into(), Value::Array(parts));
+                }
+            }
+        }
+
+        let client_type = self
+            .type_field
+            .as_ref()
+            .and_then(|field| obj.get(field))
+            .and_then(|t| t.as_str());
+        let common_type = match client_type {
+            Some(client_type) => self
+                .types
+                .iter()
+                .find(|(_, value)| value.as_str() == client_type)
+                .map(|(common, _)| common.clone())
+                .unwrap_or_else(|| client_type.to_string()),
+            None if out.contains_key("command") => "stdio".to_string(),
+            None => "http".to_string(),
+        };
+        out.insert("type".into(), json!(common_type));
+        Value::Object(out)
+    }
+
+    /// Convert a common server config into a client entry, keeping client-only keys
+    /// from the entry being replaced. The result is in the enabled state.
+    pub fn to_client(&self, cfg: &Value, previous: Option<&Value>) -> Result<Value, String> {
+        let cfg = cfg
+            .as_object()
+            .ok_or_else(|| "Server config must be an object".to_string())?;
+        let managed = self.managed_keys();
+        let mut out: Map<String, Value> = previous
+            .and_then(|p| p.as_object())
+            .map(|p| {
+                p.iter()
+                    .filter(|(key, _)| !managed.contains(&key.as_str()))
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for (key, value) in cfg {
+            match key.as_str() {
+                "type" | "disabled" | "isActive" | "enabled" => {}
+                key if MAPPED_FIELDS.contains(&key) => {}
+                _ => {
+                    out.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        let mut mapped = cfg.clone();
+        let command = if self.command_array {
+            mapped.remove("command")
+        } else {
+            None
+        };
+        if let Some(command) = command {
+            let mut parts = vec![command];
+            if let Some(args) = mapped.remove("args").and_then(|a| a.as_array().cloned()) {
+                parts.extend(args);
+            }
+            mapped.insert("command".into(), Value::Array(parts));
+        }
+        for common in MAPPED_FIELDS {
+            if let Some(value) = mapped.remove(common) {
+                out.insert(self.field(common).to_string(), value);
+            }
+        }
+
+        if let Some(type_field) = &self.type_field {
+            let common_type = match cfg.get("type").and_then(|t| t.as_str()) {
+                Some(t) => t.to_string(),
+                None if cfg.contains_key("command") => "stdio".to_string(),
+                None => "http".to_string(),
+            };
+            let client_type = self.types.get(&common_type).unwrap_or(&common_type);
+            out.insert(type_field.clone(), json!(client_type));
+        }
+        if let DisableStrategy::Field {
+            field,
+            enabled_value: Some(value),
+            ..
+        } = &self.disable
+        {
+            out.insert(field.clone(), value.clone());
+        }
+        Ok(Value::Object(out))
+    }
+
+    fn is_disabled(&self, entry: &Value) -> bool {
+        match &self.disable {
+            DisableStrategy::Field {
+                field,
+                disabled_value,
+                ..
+            } => entry.get(field) == Some(disabled_value),
+            DisableStrategy::Section { .. } => false,
+        }
+    }
+
+    fn set_state(&self, entry: &mut Value, enabled: bool) {
+        if let DisableStrategy::Field {
+            field,
+            disabled_value,
+            enabled_value,
+        } = &self.disable
+        {
+            let Some(obj) = entry.as_object_mut() else {
+                return;
+            };
+            match (enabled, enabled_value) {
+                (false, _) => {
+                    obj.insert(field.clone(), disabled_value.clone());
+                }
+                (true, Some(value)) => {
+                    obj.insert(field.clone(), value.clone());
+                }
+                (true, None) => {
+                    obj.remove(field);
+                }
+            }
+        }
+    }
+}
+
+/// Server store for a client described by a definition file
+pub struct DefinedStore {
+    def: &'static ClientDefinition,
+    path: PathBuf,
+}
+
+impl DefinedStore {
+    pub fn new(def: &'static ClientDefinition, path: Option<&str>) -> Self {
+        Self {
+            def,
+            path: ClientConfig::new(&def.id, path).get_path().to_path_buf(),
+        }
+    }
+
+    fn section(doc: &Value, key: &str) -> Map<String, Value> {
+        doc.get(key)
+            .and_then(|s| s.as_object())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn disabled_key(&self) -> Option<&str> {
+        match &self.def.disable {
+            DisableStrategy::Section { key } => Some(key),
+            DisableStrategy::Field { .. } => None,
+        }
+    }
+
+    async fn read_sections(&self) -> Result<(Map<String, Value>, Map<String, Value>), String> {
+        let doc = JsonManager::read_json_file(&self.path).await?;
+        let root = Self::section(&doc, &self.def.root_key);
+        let mut active = Map::new();
+        let mut disabled = match self.disabled_key() {
+            Some(key) => Self::section(&doc, key),
+            None => Map::new(),
+        };
+        for (name, entry) in root {
+            if self.def.is_disabled(&entry) {
+                disabled.insert(name, entry);
+            } else {
+                active.insert(name, entry);
+            }
+        }
+        Ok((active, disabled))
+    }
+
+    fn to_common_map(&self, entries: Map<String, Value>) -> Map<String, Value> {
+        entries
+            .into_iter()
+            .map(|(name, entry)| {
+                let cfg = self.def.to_common(&entry);
+                (name, cfg)
+            })
+            .collect()
+    }
+
+    /// Load, mutate and write back the root and disabled maps; other keys are kept
+    async fn edit<F>(&self, edit: F) -> Result<(), String>
+    where
+        F: FnOnce(&mut Map<String, Value>, &mut Map<String, Value>) -> Result<(), String>,
+    {
+        let _guard = DEFINED_CFG_LOCK.lock().await;
+        let mut doc = JsonManager::read_json_file(&self.path).await?;
+        if !doc.is_object() {
+            doc = json!({});
+        }
+        let mut root = Self::section(&doc, &self.def.root_key);
+        let mut disabled = match self.disabled_key() {
+            Some(key) => Self::section(&doc, key),
+            None => Map::new(),
+        };
+        edit(&mut root, &mut disabled)?;
+
+        doc[&self.def.root_key] = Value::Object(root);
+        if let Some(key) = self.disabled_key() {
+            if disabled.is_empty() {
+                doc.as_object_mut().map(|obj| obj.remove(key));
+            } else {
+                doc[key] = Value::Object(disabled);
+            }
+        }
+        JsonManager::write_json_file(&self.path, &doc).await
+    }
+
+    /// Store `entry` for `name` in the requested state
+    fn place(
+        &self,
+        root: &mut Map<String, Value>,
+        disabled: &mut Map<String, Value>,
+        name: &str,
+        mut entry: Value,
+        enabled: bool,
+    ) {
+        self.def.set_state(&mut entry, enabled);
+        match self.disabled_key() {
+            Some(_) if !enabled => {
+                root.remove(name);
+                disabled.insert(name.to_string(), entry);
+            }
+            _ => {
+                disabled.remove(name);
+                root.insert(name.to_string(), entry);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ServerStore for DefinedStore {
+    fn client(&self) -> &str {
+        &self.def.id
+    }
+
+    async fn read_servers(&self) -> Result<Map<String, Value>, String> {
+        let (active, _) = self.read_sections().await?;
+        Ok(self.to_common_map(active))
+    }
+
+    async fn list_disabled(&self) -> Result<Map<String, Value>, String> {
+        let (_, disabled) = self.read_sections().await?;
+        Ok(self.to_common_map(disabled))
+    }
+
+    async fn upsert(&self, name: &str, cfg: Value) -> Result<(), String> {
+        self.edit(|root, disabled| {
+            let previous = root.get(name).or_else(|| disabled.get(name));
+            let entry = self.def.to_client(&cfg, previous)?;
+            self.place(root, disabled, name, entry, true);
+            Ok(())
+        })
+        .await
+    }
+
+    async fn remove(&self, name: &str) -> Result<(), String> {
+        self.edit(|root, disabled| {
+            root.remove(name)
+                .or_else(|| disabled.remove(name))
+                .map(|_| ())
+                .ok_or_else(|| format!("MCP server '{}' not found", name))
+        })
+        .await
+    }
+
+    async fn set_enabled(&self, name: &str, enabled: bool) -> Result<(), String> {
+        self.edit(|root, disabled| {
+            let entry = root
+                .get(name)
+                .or_else(|| disabled.get(name))
+                .cloned()
+                .ok_or_else(|| format!("MCP server '{}' not found", name))?;
+            self.place(root, disabled, name, entry, enabled);
+            Ok(())
+        })
+        .await
+    }
+
+    async fn update_disabled(&self, name: &str, cfg: Value) -> Result<(), String> {
+        self.edit(|root, disabled| {
+            let previous = disabled.get(name).or_else(|| root.get(name));
+            let entry = self.def.to_client(&cfg, previous)?;
+            self.place(root, disabled, name, entry, false);
+            Ok(())
+        })
+        .await
+    }
+
+    async fn replace_all(
+        &self,
+        active: &Map<String, Value>,
+        disabled_servers: &Map<String, Value>,
+    ) -> Result<(), String> {
+        self.edit(|root, disabled| {
+            let previous_root = std::mem::take(root);
+            let previous_disabled = std::mem::take(disabled);
+            for (servers, enabled) in [(active, true), (disabled_servers, false)] {
+                for (name, cfg) in servers {
+                    let previous = previous_root
+                        .get(name)
+                        .or_else(|| previous_disabled.get(name));
+                    let entry = self.def.to_client(cfg, previous)?;
+                    self.place(root, disabled, name, entry, enabled);
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+}
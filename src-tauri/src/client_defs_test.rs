@@ -0,0 +1,76 @@
+// Tests for declarative client definitions
+use crate::client_defs::{definition, DefinedStore};
+use crate::store::ServerStore;
+use serde_json::json;
+
+#[test]
+fn test_opencode_mapping() {
+    let def = definition("opencode").unwrap();
+    let entry = json!({
+        "type": "local", "command": ["npx", "-y", "server-github"],
+        "environment": {"TOKEN": "x"}, "enabled": true, "timeout": 5000
+    });
+    let common = def.to_common(&entry);
+    assert_eq!(
+        common,
+        json!({"type": "stdio", "command": "npx", "args": ["-y", "server-github"], "env": {"TOKEN": "x"}, "timeout": 5000})
+    );
+    assert_eq!(def.to_client(&common, Some(&entry)).unwrap(), entry);
+
+    let remote = def
+        .to_client(
+            &json!({"type": "sse", "url": "https://mcp.example.com"}),
+            None,
+        )
+        .unwrap();
+    assert_eq!(remote["type"], "remote");
+    assert_eq!(def.to_common(&remote)["url"], "https://mcp.example.com");
+}
+
+#[test]
+fn test_lmstudio_has_no_type_field() {
+    let def = definition("lmstudio").unwrap();
+    let entry = def
+        .to_client(
+            &json!({"type": "http", "url": "https://mcp.example.com"}),
+            None,
+        )
+        .unwrap();
+    assert_eq!(entry, json!({"url": "https://mcp.example.com"}));
+    assert_eq!(def.to_common(&entry)["type"], "http");
+}
+
+#[tokio::test]
+async fn test_opencode_disable_keeps_entry_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("opencode.json");
+    std::fs::write(
+        &path,
+        r#"{"$schema": "https://opencode.ai/config.json", "theme": "dark"}"#,
+    )
+    .unwrap();
+
+    let store = DefinedStore::new(definition("opencode").unwrap(), path.to_str());
+    store
+        .upsert(
+            "fetch",
+            json!({"command": "uvx", "args": ["mcp-server-fetch"]}),
+        )
+        .await
+        .unwrap();
+    store.set_enabled("fetch", false).await.unwrap();
+
+    assert!(store.read_servers().await.unwrap().is_empty());
+    assert_eq!(
+        store.list_disabled().await.unwrap()["fetch"]["command"],
+        "uvx"
+    );
+    let doc: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(doc["theme"], "dark");
+    assert_eq!(doc["mcp"]["fetch"]["enabled"], false);
+    assert_eq!(
+        doc["mcp"]["fetch"]["command"],
+        json!(["uvx", "mcp-server-fetch"])
+    );
+}
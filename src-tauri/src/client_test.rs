@@ -0,0 +1,39 @@
+// Tests for config path resolution
+use crate::client::ClientConfig;
+use std::path::PathBuf;
+
+#[test]
+fn test_config_files_are_used_as_is() {
+    // Discovery and the inventory pass the file they found, e.g. an XDG variant of
+    // Claude Desktop's config or a Cursor mcp.json outside the home dir
+    for (client, file) in [
+        ("claude", "/etc/xdg/Claude/claude_desktop_config.json"),
+        ("cursor", "/work/app/.cursor/mcp.json"),
+        ("gemini", "/work/app/.gemini/settings.json"),
+        ("zed", "/home/me/.config/zed/settings.json"),
+        ("mcplinker", "/home/me/.config/mcplinker/mcp.json"),
+    ] {
+        assert_eq!(
+            ClientConfig::new(client, Some(file)).path,
+            PathBuf::from(file),
+            "{}",
+            client
+        );
+    }
+}
+
+#[test]
+fn test_project_dirs_still_resolve_to_client_files() {
+    assert_eq!(
+        ClientConfig::new("cursor", Some("/work/app")).path,
+        PathBuf::from("/work/app/.cursor/mcp.json")
+    );
+    assert_eq!(
+        ClientConfig::new("zed", Some("/work/app")).path,
+        PathBuf::from("/work/app/.zed/settings.json")
+    );
+    assert_eq!(
+        ClientConfig::new("goose", Some("/home/me/.config/goose/config.yaml")).path,
+        PathBuf::from("/home/me/.config/goose/config.yaml")
+    );
+}
@@ -1,8 +1,12 @@
 use crate::client::ClientConfig;
 use crate::codex as codex_cmds;
+use crate::gemini;
+use crate::inventory::CANONICAL_CLIENT;
+use crate::json_manager::revision::{take_revision, with_revision};
+use crate::json_manager::utils::common_to_copilot;
 use crate::json_manager::JsonManager;
+use crate::store::store_for;
 use serde_json::{json, Value};
-use std::path::PathBuf;
 
 #[tauri::command]
 pub async fn read_json_file(client_name: String, path: Option<String>) -> Result<Value, String> {
@@ -13,11 +17,15 @@ pub async fn read_json_file(client_name: String, path: Option<String>) -> Result
         return Ok(json);
     }
 
+    if let Some(store) = store_for(&client_name, path.as_deref()) {
+        return store.read_document().await;
+    }
+
     let app_config = ClientConfig::new(&client_name, path.as_deref());
     let file_path = app_config.get_path();
 
     // Remove the file existence check - let JsonManager handle it
-    let mut json = JsonManager::read_json_file(file_path).await?;
+    let (mut json, revision) = JsonManager::read_json_file_at(file_path, None).await?;
 
     // Ensure the JSON always has the required structure
     if !json.is_object() {
@@ -40,7 +48,12 @@ pub async fn read_json_file(client_name: String, path: Option<String>) -> Result
         }
     }
 
-    Ok(json)
+    if client_name == "gemini" {
+        gemini::map_server_sections(&mut json, gemini::gemini_to_common);
+    }
+
+    // Handed back by `write_json_file` so it can refuse to clobber newer edits
+    Ok(with_revision(json, revision))
 }
 
 #[tauri::command]
@@ -51,8 +64,43 @@ pub async fn write_json_file(
 ) -> Result<(), String> {
     let app_config = ClientConfig::new(&client_name, path.as_deref());
     let file_path = app_config.get_path();
+    let mut content = content;
+    let expected_revision = take_revision(&mut content);
+
+    if let Some(store) = store_for(&client_name, path.as_deref()) {
+        // Only the server section is ours to write; keep the rest of the document
+        let empty = serde_json::Map::new();
+        let active = content
+            .get("mcpServers")
+            .and_then(|v| v.as_object())
+            .unwrap_or(&empty);
+        let disabled = content
+            .get("__disabled")
+            .and_then(|v| v.as_object())
+            .unwrap_or(&empty);
+        return store.replace_all(active, disabled).await;
+    }
 
-    JsonManager::write_json_file(file_path, &content).await
+    if client_name == "gemini" {
+        gemini::map_server_sections(&mut content, gemini::common_to_gemini);
+    }
+    if client_name == "copilot" {
+        // `read_json_file` hands out both keys; `mcpServers` is the one callers edit
+        common_to_copilot(&mut content);
+        if content.get("inputs").is_none() {
+            let existing = JsonManager::read_json_file(file_path).await?;
+            if let Some(inputs) = existing.get("inputs") {
+                content["inputs"] = inputs.clone();
+            }
+        }
+    }
+
+    match expected_revision {
+        Some(revision) => JsonManager::write_json_file_at(file_path, &content, Some(&revision))
+            .await
+            .map(|_| ()),
+        None => JsonManager::write_json_file(file_path, &content).await,
+    }
 }
 
 #[tauri::command]
@@ -65,7 +113,8 @@ pub async fn get_app_path(client_name: String, path: Option<String>) -> Result<S
 
 #[tauri::command]
 pub fn check_mcplinker_config_exists() -> bool {
-    let home_dir = dirs::home_dir().unwrap_or_default();
-    let config_path: PathBuf = home_dir.join(".config/mcplinker/mcp.json");
-    config_path.exists()
+    // The canonical store that drift detection compares every client with
+    ClientConfig::new(CANONICAL_CLIENT, None)
+        .get_path()
+        .exists()
 }
@@ -38,7 +38,7 @@ pub enum McpServerConfig {
 }
 
 impl McpServerConfig {
-    fn is_enabled(&self) -> bool {
+    pub(crate) fn is_enabled(&self) -> bool {
         match self {
             McpServerConfig::Stdio { enabled, .. } => *enabled,
             McpServerConfig::Http { enabled, .. } => *enabled,
@@ -141,6 +141,7 @@ async fn persist_document(config_path: &Path, doc: DocumentMut) -> Result<(), St
         .await
         .map_err(|e| format!("Failed to flush temp file: {}", e))?;
     drop(file);
+    crate::config_watcher::note_self_write(config_path, toml_content.as_bytes());
     fs::rename(&tmp_path, config_path)
         .await
         .map_err(|e| format!("Failed to rename temp file: {}", e))?;
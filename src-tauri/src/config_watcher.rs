@@ -0,0 +1,344 @@
+use crate::claude_code_commands::read_claude_config;
+use crate::discovery::discover_clients;
+use crate::inventory::{config_hash, read_client_servers, ClientServers};
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, Mutex};
+
+/// Emitted with a `ConfigChangedEvent` when a client config changes outside mcp-linker
+pub const CONFIG_CHANGED_EVENT: &str = "client-config-changed";
+
+/// Editors and clients often write a file in several steps; wait for them to settle
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Hash of what mcp-linker last wrote to each path, `None` for a file it removed.
+/// Events are only skipped while the file still holds exactly that.
+static SELF_WRITES: Lazy<std::sync::Mutex<HashMap<PathBuf, Option<u64>>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+static WATCHER: Lazy<Mutex<Option<WatcherState>>> = Lazy::new(|| Mutex::new(None));
+
+/// Kept so a refresh can build a new debouncer feeding the same event loop
+static WATCH_SENDER: Lazy<Mutex<Option<mpsc::UnboundedSender<Vec<PathBuf>>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ServerDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Same name, different config (enabled state aside)
+    pub modified: Vec<String>,
+    pub enabled: Vec<String>,
+    pub disabled: Vec<String>,
+}
+
+impl ServerDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.enabled.is_empty()
+            && self.disabled.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigChangedEvent {
+    pub client: String,
+    pub variant: Option<String>,
+    pub path: String,
+    pub diff: ServerDiff,
+    /// Set when the file no longer parses; the last good state is kept
+    pub error: Option<String>,
+}
+
+struct WatchedConfig {
+    client: String,
+    variant: Option<String>,
+    path: PathBuf,
+    servers: ClientServers,
+}
+
+impl WatchedConfig {
+    /// Continue keeps one file per server, so its config is a whole directory. Temp
+    /// files of an atomic write are followed by the rename, which is what counts.
+    fn covers(&self, changed: &Path) -> bool {
+        if changed.extension().is_some_and(|ext| ext == "tmp") {
+            return false;
+        }
+        changed == self.path || (self.path.is_dir() && changed.starts_with(&self.path))
+    }
+}
+
+struct WatcherState {
+    // Dropping the debouncer stops the watch
+    _debouncer: Debouncer<RecommendedWatcher>,
+    configs: Vec<WatchedConfig>,
+}
+
+fn content_hash(content: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn note(path: &Path, hash: Option<u64>) {
+    if let Ok(mut writes) = SELF_WRITES.lock() {
+        writes.insert(path.to_path_buf(), hash);
+    }
+}
+
+/// Record that mcp-linker is about to write `content` to `path`, so the watcher does
+/// not report it
+pub(crate) fn note_self_write(path: &Path, content: &[u8]) {
+    note(path, Some(content_hash(content)));
+}
+
+/// Record that mcp-linker is about to delete `path`
+pub(crate) fn note_self_removal(path: &Path) {
+    note(path, None);
+}
+
+/// Record that mcp-linker is about to rename `from` to `to`
+pub(crate) fn note_self_rename(from: &Path, to: &Path) {
+    match std::fs::read(from) {
+        Ok(content) => note_self_write(to, &content),
+        Err(_) => note_self_removal(to),
+    }
+    note_self_removal(from);
+}
+
+/// Whether `path` still holds what mcp-linker last wrote to it. Once it does not,
+/// the record is dropped, so later events on the path are reported.
+pub(crate) fn is_self_write(path: &Path) -> bool {
+    let Ok(mut writes) = SELF_WRITES.lock() else {
+        return false;
+    };
+    let Some(expected) = writes.get(path) else {
+        return false;
+    };
+    let current = std::fs::read(path)
+        .ok()
+        .map(|content| content_hash(&content));
+    if current == *expected {
+        true
+    } else {
+        writes.remove(path);
+        false
+    }
+}
+
+/// Server-level difference between two reads of the same client
+pub(crate) fn diff_servers(before: &ClientServers, after: &ClientServers) -> ServerDiff {
+    let mut diff = ServerDiff::default();
+    for name in after.active.keys().chain(after.disabled.keys()) {
+        let Some((new_cfg, new_state)) = after.get(name) else {
+            continue;
+        };
+        match before.get(name) {
+            None => diff.added.push(name.clone()),
+            Some((old_cfg, old_state)) => {
+                if config_hash(old_cfg) != config_hash(new_cfg) {
+                    diff.modified.push(name.clone());
+                }
+                if old_state != new_state {
+                    if after.disabled.contains_key(name) {
+                        diff.disabled.push(name.clone());
+                    } else {
+                        diff.enabled.push(name.clone());
+                    }
+                }
+            }
+        }
+    }
+    for name in before.active.keys().chain(before.disabled.keys()) {
+        if after.get(name).is_none() {
+            diff.removed.push(name.clone());
+        }
+    }
+    for names in [
+        &mut diff.added,
+        &mut diff.removed,
+        &mut diff.modified,
+        &mut diff.enabled,
+        &mut diff.disabled,
+    ] {
+        names.sort();
+    }
+    diff
+}
+
+/// Every server in `~/.claude.json`: the user scope under its name, and the local scope
+/// of each project as `<project dir>:<name>`
+pub(crate) fn claude_code_servers(config: &Value) -> ClientServers {
+    let servers_of = |section: &Value| {
+        section
+            .get("mcpServers")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let mut active = servers_of(config);
+    if let Some(projects) = config.get("projects").and_then(Value::as_object) {
+        for (dir, project) in projects {
+            for (name, cfg) in servers_of(project) {
+                active.insert(format!("{}:{}", dir, name), cfg);
+            }
+        }
+    }
+    ClientServers {
+        active,
+        disabled: Map::new(),
+    }
+}
+
+/// Servers of a watched config. Inventory and drift only look at Claude Code's user
+/// scope, but `claude mcp add` writes to a project by default, so all of them count here.
+pub(crate) async fn read_watched_servers(
+    client: &str,
+    path: &str,
+) -> Result<ClientServers, String> {
+    if client == "claude_code" {
+        return read_claude_config(Path::new(path)).map(|config| claude_code_servers(&config));
+    }
+    read_client_servers(client, Some(path)).await
+}
+
+/// Discover client configs, read their current servers and watch the files
+async fn build_state(tx: mpsc::UnboundedSender<Vec<PathBuf>>) -> Result<WatcherState, String> {
+    let mut debouncer = new_debouncer(DEBOUNCE, move |result: DebounceEventResult| match result {
+        Ok(events) => {
+            let _ = tx.send(events.into_iter().map(|event| event.path).collect());
+        }
+        Err(e) => println!("[ConfigWatcher] watch error: {}", e),
+    })
+    .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+    let mut configs = Vec::new();
+    let mut watched: HashSet<PathBuf> = HashSet::new();
+    for found in discover_clients().await? {
+        let path = PathBuf::from(&found.path);
+        // Files are replaced by rename on save, so watch the parent dir, not the inode
+        let (target, mode) = if path.is_dir() {
+            (path.clone(), RecursiveMode::Recursive)
+        } else {
+            match path.parent() {
+                Some(parent) => (parent.to_path_buf(), RecursiveMode::NonRecursive),
+                None => continue,
+            }
+        };
+        // Several configs can share a parent dir; it is only watched once
+        let watch = if watched.insert(target.clone()) {
+            debouncer.watcher().watch(&target, mode)
+        } else {
+            Ok(())
+        };
+        if let Err(e) = watch {
+            println!("[ConfigWatcher] cannot watch {}: {}", target.display(), e);
+            continue;
+        }
+        let servers = read_watched_servers(&found.client, &found.path)
+            .await
+            .unwrap_or_default();
+        configs.push(WatchedConfig {
+            client: found.client,
+            variant: found.variant,
+            path,
+            servers,
+        });
+    }
+
+    println!(
+        "[ConfigWatcher] watching {} client configs in {} dirs",
+        configs.len(),
+        watched.len()
+    );
+    Ok(WatcherState {
+        _debouncer: debouncer,
+        configs,
+    })
+}
+
+async fn handle_changes(app: &AppHandle, changed: Vec<PathBuf>) {
+    let mut guard = WATCHER.lock().await;
+    let Some(state) = guard.as_mut() else {
+        return;
+    };
+    for config in state.configs.iter_mut() {
+        let paths: Vec<&PathBuf> = changed.iter().filter(|p| config.covers(p)).collect();
+        if paths.is_empty() {
+            continue;
+        }
+        // Still re-read our own writes so the next diff starts from them. Directory
+        // events (Continue) say nothing by themselves; their files decide.
+        let own_write = paths.iter().all(|p| p.is_dir() || is_self_write(p));
+        let path = config.path.to_string_lossy().to_string();
+        let (diff, error) = match read_watched_servers(&config.client, &path).await {
+            Ok(servers) => {
+                let diff = diff_servers(&config.servers, &servers);
+                config.servers = servers;
+                (diff, None)
+            }
+            Err(e) => (ServerDiff::default(), Some(e)),
+        };
+        if own_write || (diff.is_empty() && error.is_none()) {
+            continue;
+        }
+
+        println!(
+            "[ConfigWatcher] {} changed externally: {:?}",
+            config.client, diff
+        );
+        let event = ConfigChangedEvent {
+            client: config.client.clone(),
+            variant: config.variant.clone(),
+            path,
+            diff,
+            error,
+        };
+        if let Err(e) = app.emit(CONFIG_CHANGED_EVENT, event) {
+            println!("[ConfigWatcher] failed to emit event: {}", e);
+        }
+    }
+}
+
+/// Start watching every discovered client config; called once from app setup
+pub async fn start_config_watcher(app: AppHandle) -> Result<(), String> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<Vec<PathBuf>>();
+    *WATCHER.lock().await = Some(build_state(tx.clone()).await?);
+    *WATCH_SENDER.lock().await = Some(tx);
+
+    tauri::async_runtime::spawn(async move {
+        while let Some(changed) = rx.recv().await {
+            handle_changes(&app, changed).await;
+        }
+    });
+    Ok(())
+}
+
+/// Re-run discovery and watch the resulting configs, e.g. after a client was installed
+#[tauri::command]
+pub async fn refresh_config_watcher() -> Result<Vec<String>, String> {
+    let tx = WATCH_SENDER
+        .lock()
+        .await
+        .clone()
+        .ok_or("Config watcher is not running")?;
+    let state = build_state(tx).await?;
+    let paths = state
+        .configs
+        .iter()
+        .map(|config| config.path.to_string_lossy().to_string())
+        .collect();
+    *WATCHER.lock().await = Some(state);
+    Ok(paths)
+}
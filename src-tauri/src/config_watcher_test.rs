@@ -0,0 +1,102 @@
+// Tests for the config watcher's server diff and self-write suppression
+use crate::config_watcher::{
+    diff_servers, is_self_write, note_self_removal, note_self_rename, note_self_write,
+    read_watched_servers,
+};
+use crate::inventory_test::servers;
+use serde_json::json;
+
+#[test]
+fn test_diff_reports_server_level_changes() {
+    let before = servers(
+        json!({
+            "github": {"command": "npx", "env": {"TOKEN": "a"}},
+            "fetch": {"command": "uvx"},
+            "old": {"command": "old"}
+        }),
+        json!({"search": {"url": "https://example.com/mcp"}}),
+    );
+    let after = servers(
+        json!({
+            "github": {"command": "npx", "env": {"TOKEN": "b"}},
+            "search": {"url": "https://example.com/mcp"},
+            "new": {"command": "new"}
+        }),
+        json!({"fetch": {"command": "uvx"}}),
+    );
+
+    let diff = diff_servers(&before, &after);
+    assert_eq!(diff.added, vec!["new"]);
+    assert_eq!(diff.removed, vec!["old"]);
+    assert_eq!(diff.modified, vec!["github"]);
+    assert_eq!(diff.enabled, vec!["search"]);
+    assert_eq!(diff.disabled, vec!["fetch"]);
+
+    // Bookkeeping keys alone are not a change
+    let touched = servers(
+        json!({
+            "github": {"command": "npx", "env": {"TOKEN": "b"}, "updated_at": "now"},
+            "search": {"url": "https://example.com/mcp"},
+            "new": {"command": "new"}
+        }),
+        json!({"fetch": {"command": "uvx"}}),
+    );
+    assert!(diff_servers(&after, &touched).is_empty());
+}
+
+#[test]
+fn test_only_content_we_wrote_counts_as_self_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mcp.json");
+    let ours = br#"{"mcpServers": {}}"#;
+    std::fs::write(&path, ours).unwrap();
+    assert!(!is_self_write(&path));
+
+    note_self_write(&path, ours);
+    assert!(is_self_write(&path));
+    assert!(!is_self_write(&dir.path().join("other.json")));
+
+    // An external edit right after ours is reported, and so is everything after it
+    std::fs::write(&path, br#"{"mcpServers": {"x": {"command": "x"}}}"#).unwrap();
+    assert!(!is_self_write(&path));
+    std::fs::write(&path, ours).unwrap();
+    assert!(!is_self_write(&path));
+
+    let block = dir.path().join("fetch.yaml");
+    let disabled = dir.path().join("fetch.yaml.disabled");
+    std::fs::write(&block, "name: fetch\n").unwrap();
+    note_self_rename(&block, &disabled);
+    std::fs::rename(&block, &disabled).unwrap();
+    assert!(is_self_write(&block));
+    assert!(is_self_write(&disabled));
+
+    note_self_removal(&disabled);
+    std::fs::remove_file(&disabled).unwrap();
+    assert!(is_self_write(&disabled));
+}
+
+#[tokio::test]
+async fn test_project_scoped_claude_code_servers_are_diffed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(".claude.json");
+    let path_str = path.to_str().unwrap();
+    let mut config = json!({
+        "mcpServers": {"fetch": {"command": "uvx"}},
+        "projects": {
+            "/repo": {"mcpServers": {"local": {"command": "./run.sh"}}},
+            "/empty": {"allowedTools": []}
+        }
+    });
+    std::fs::write(&path, config.to_string()).unwrap();
+    let before = read_watched_servers("claude_code", path_str).await.unwrap();
+
+    config["projects"]["/repo"]["mcpServers"]["local"]["args"] = json!(["--verbose"]);
+    config["projects"]["/empty"]["mcpServers"] = json!({"sentry": {"url": "https://x"}});
+    std::fs::write(&path, config.to_string()).unwrap();
+    let after = read_watched_servers("claude_code", path_str).await.unwrap();
+
+    let diff = diff_servers(&before, &after);
+    assert_eq!(diff.modified, vec!["/repo:local"]);
+    assert_eq!(diff.added, vec!["/empty:sentry"]);
+    assert!(diff.removed.is_empty());
+}
@@ -0,0 +1,500 @@
+use crate::client::ClientConfig;
+use crate::config_watcher::{note_self_removal, note_self_rename};
+use crate::goose::{load_document, persist_document};
+use crate::store::ServerStore;
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::sync::Mutex;
+
+// Continue keeps MCP servers as blocks, one YAML file each, in `.continue/mcpServers/`:
+// name: GitHub
+// version: 0.0.1
+// schema: v1
+// mcpServers:
+//   - name: github
+//     command: npx
+//     args: [-y, "@modelcontextprotocol/server-github"]
+// Servers may also be listed inline in `.continue/config.yaml` under `mcpServers`.
+// A disabled server is a block file renamed to `<file>.disabled`, which Continue ignores.
+
+const SERVERS_DIR: &str = "mcpServers";
+const CONFIG_FILE: &str = "config.yaml";
+const DISABLED_SUFFIX: &str = ".disabled";
+/// Blocks written by `replace_all` before the old ones go; Continue does not load them
+const STAGED_SUFFIX: &str = ".staged";
+
+static CONTINUE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Convert a Continue `mcpServers` entry into the common shape
+pub(crate) fn continue_to_common(entry: &Value) -> Option<Value> {
+    let mut out = entry.as_object()?.clone();
+    out.remove("name");
+    if out.contains_key("uses") {
+        return None;
+    }
+
+    let server_type = match out.get("type").and_then(|t| t.as_str()) {
+        Some("streamable-http") => "http".to_string(),
+        Some(other) => other.to_string(),
+        None if out.contains_key("command") => "stdio".to_string(),
+        None if out.contains_key("url") => "http".to_string(),
+        None => return None,
+    };
+    out.insert("type".into(), json!(server_type));
+
+    let headers = out
+        .get_mut("requestOptions")
+        .and_then(|o| o.as_object_mut())
+        .and_then(|o| o.remove("headers"));
+    if let Some(headers) = headers {
+        out.insert("headers".into(), headers);
+    }
+    let drop_options = out
+        .get("requestOptions")
+        .and_then(|o| o.as_object())
+        .is_some_and(|o| o.is_empty());
+    if drop_options {
+        out.remove("requestOptions");
+    }
+    Some(Value::Object(out))
+}
+
+/// Convert a common server config into a Continue entry, keeping Continue-only keys
+/// (cwd, connectionTimeout, requestOptions...) from the entry being replaced.
+pub(crate) fn common_to_continue(
+    name: &str,
+    cfg: &Value,
+    previous: Option<&Value>,
+) -> Result<Value, String> {
+    let cfg = cfg
+        .as_object()
+        .ok_or_else(|| "Server config must be an object".to_string())?;
+    let mut out = previous
+        .and_then(|p| p.as_object())
+        .cloned()
+        .unwrap_or_default();
+    for key in ["type", "command", "args", "env", "url"] {
+        out.remove(key);
+    }
+    if let Some(options) = out
+        .get_mut("requestOptions")
+        .and_then(|o| o.as_object_mut())
+    {
+        options.remove("headers");
+    }
+
+    for (key, value) in cfg {
+        match key.as_str() {
+            "name" | "disabled" | "isActive" | "enabled" | "headers" | "type" => {}
+            _ => {
+                out.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    let server_type = if cfg.contains_key("command") {
+        "stdio"
+    } else if cfg.contains_key("url") {
+        match cfg.get("type").and_then(|t| t.as_str()) {
+            Some("sse") => "sse",
+            _ => "streamable-http",
+        }
+    } else {
+        return Err("Continue servers need a `command` or `url`".to_string());
+    };
+    out.insert("type".into(), json!(server_type));
+    out.insert("name".into(), json!(name));
+
+    if let Some(headers) = cfg.get("headers").filter(|h| h.is_object()) {
+        let options = out
+            .entry("requestOptions")
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .ok_or_else(|| "`requestOptions` must be an object".to_string())?;
+        options.insert("headers".into(), headers.clone());
+    }
+    let drop_options = out
+        .get("requestOptions")
+        .and_then(|o| o.as_object())
+        .is_some_and(|o| o.is_empty());
+    if drop_options {
+        out.remove("requestOptions");
+    }
+    Ok(Value::Object(out))
+}
+
+/// One YAML document holding `mcpServers` entries: a block file or `config.yaml`
+struct Source {
+    path: PathBuf,
+    disabled: bool,
+    is_block: bool,
+    doc: serde_yaml_ng::Value,
+}
+
+impl Source {
+    fn entries(&self) -> Vec<Value> {
+        self.doc
+            .get(SERVERS_DIR)
+            .and_then(|s| serde_json::to_value(s).ok())
+            .and_then(|s| s.as_array().cloned())
+            .unwrap_or_default()
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries()
+            .iter()
+            .position(|e| e.get("name").and_then(|n| n.as_str()) == Some(name))
+    }
+
+    fn set_entries(&mut self, entries: Vec<Value>) -> Result<(), String> {
+        let yaml = serde_yaml_ng::to_value(&entries)
+            .map_err(|e| format!("Failed to convert servers to YAML: {}", e))?;
+        self.doc
+            .as_mapping_mut()
+            .ok_or_else(|| format!("{} is not a YAML mapping", self.path.display()))?
+            .insert(serde_yaml_ng::Value::from(SERVERS_DIR), yaml);
+        Ok(())
+    }
+}
+
+fn is_block_file(file_name: &str) -> Option<bool> {
+    let (stem, disabled) = match file_name.strip_suffix(DISABLED_SUFFIX) {
+        Some(stem) => (stem, true),
+        None => (file_name, false),
+    };
+    if stem.ends_with(".yaml") || stem.ends_with(".yml") {
+        Some(disabled)
+    } else {
+        None
+    }
+}
+
+async fn load_sources(dir: &Path) -> Result<Vec<Source>, String> {
+    let mut sources = Vec::new();
+
+    let config_path = dir.join(CONFIG_FILE);
+    if config_path.is_file() {
+        sources.push(Source {
+            doc: load_document(&config_path).await?,
+            path: config_path,
+            disabled: false,
+            is_block: false,
+        });
+    }
+
+    let servers_dir = dir.join(SERVERS_DIR);
+    if !servers_dir.is_dir() {
+        return Ok(sources);
+    }
+    let mut paths = Vec::new();
+    let mut read_dir = fs::read_dir(&servers_dir)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", servers_dir.display(), e))?;
+    while let Some(entry) = read_dir
+        .next_entry()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", servers_dir.display(), e))?
+    {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if let Some(disabled) = is_block_file(&file_name) {
+            paths.push((entry.path(), disabled));
+        }
+    }
+    paths.sort();
+
+    for (path, disabled) in paths {
+        sources.push(Source {
+            doc: load_document(&path).await?,
+            path,
+            disabled,
+            is_block: true,
+        });
+    }
+    Ok(sources)
+}
+
+fn collect(sources: &[Source], disabled: bool) -> Map<String, Value> {
+    let mut servers = Map::new();
+    for source in sources.iter().filter(|s| s.disabled == disabled) {
+        for entry in source.entries() {
+            let name = match entry.get("name").and_then(|n| n.as_str()) {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+            if let Some(cfg) = continue_to_common(&entry) {
+                servers.entry(name).or_insert(cfg);
+            }
+        }
+    }
+    servers
+}
+
+fn find(sources: &[Source], name: &str, disabled: bool) -> Option<(usize, usize)> {
+    sources
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.disabled == disabled)
+        .find_map(|(i, s)| s.position(name).map(|pos| (i, pos)))
+}
+
+/// Lowercase file stem for a server name, e.g. "My Server" -> "my-server"
+fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let stem = stem.trim_matches('-');
+    if stem.is_empty() {
+        "server".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+fn block_path(path: &Path, disabled: bool) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let base = file_name
+        .strip_suffix(DISABLED_SUFFIX)
+        .unwrap_or(&file_name);
+    if disabled {
+        path.with_file_name(format!("{}{}", base, DISABLED_SUFFIX))
+    } else {
+        path.with_file_name(base)
+    }
+}
+
+/// Pick `<stem>.yaml`, `<stem>-2.yaml`... that is free in either state
+fn new_block_path(dir: &Path, name: &str, disabled: bool) -> PathBuf {
+    let servers_dir = dir.join(SERVERS_DIR);
+    let stem = file_stem(name);
+    let mut candidate = servers_dir.join(format!("{}.yaml", stem));
+    let mut n = 2;
+    while candidate.exists() || block_path(&candidate, true).exists() {
+        candidate = servers_dir.join(format!("{}-{}.yaml", stem, n));
+        n += 1;
+    }
+    block_path(&candidate, disabled)
+}
+
+fn block_document(name: &str, entry: Value) -> Result<serde_yaml_ng::Value, String> {
+    serde_yaml_ng::to_value(json!({
+        "name": name,
+        "version": "0.0.1",
+        "schema": "v1",
+        SERVERS_DIR: [entry],
+    }))
+    .map_err(|e| format!("Failed to convert block to YAML: {}", e))
+}
+
+async fn write_new_block(
+    dir: &Path,
+    name: &str,
+    entry: Value,
+    disabled: bool,
+) -> Result<(), String> {
+    let doc = block_document(name, entry)?;
+    persist_document(&new_block_path(dir, name, disabled), &doc).await
+}
+
+/// Write a block under a hidden `.staged` name, to be renamed into place later
+async fn stage_block(dir: &Path, index: usize, name: &str, cfg: &Value) -> Result<PathBuf, String> {
+    let doc = block_document(name, common_to_continue(name, cfg, None)?)?;
+    let path =
+        dir.join(SERVERS_DIR)
+            .join(format!(".{}-{}{}", file_stem(name), index, STAGED_SUFFIX));
+    persist_document(&path, &doc).await?;
+    Ok(path)
+}
+
+/// Drop entry `pos` from a source; a block file left without servers is deleted
+async fn remove_entry(source: &mut Source, pos: usize) -> Result<Value, String> {
+    let mut entries = source.entries();
+    let removed = entries.remove(pos);
+    if source.is_block && entries.is_empty() {
+        note_self_removal(&source.path);
+        fs::remove_file(&source.path)
+            .await
+            .map_err(|e| format!("Failed to remove {}: {}", source.path.display(), e))?;
+    } else {
+        source.set_entries(entries)?;
+        persist_document(&source.path, &source.doc).await?;
+    }
+    Ok(removed)
+}
+
+/// Move a server to the given state: single-server blocks are renamed, anything else
+/// is split out into a block file of its own.
+async fn move_entry(
+    dir: &Path,
+    source: &mut Source,
+    pos: usize,
+    name: &str,
+    entry: Value,
+    disabled: bool,
+) -> Result<(), String> {
+    if source.is_block && source.entries().len() == 1 {
+        let target = block_path(&source.path, disabled);
+        if !target.exists() {
+            let mut entries = source.entries();
+            entries[0] = entry;
+            source.set_entries(entries)?;
+            persist_document(&source.path, &source.doc).await?;
+            note_self_rename(&source.path, &target);
+            return fs::rename(&source.path, &target)
+                .await
+                .map_err(|e| format!("Failed to rename {}: {}", source.path.display(), e));
+        }
+    }
+    write_new_block(dir, name, entry, disabled).await?;
+    remove_entry(source, pos).await.map(|_| ())
+}
+
+async fn upsert_in_state(
+    dir: &Path,
+    name: &str,
+    cfg: &Value,
+    disabled: bool,
+) -> Result<(), String> {
+    let mut sources = load_sources(dir).await?;
+    if let Some((i, pos)) = find(&sources, name, disabled) {
+        let source = &mut sources[i];
+        let mut entries = source.entries();
+        entries[pos] = common_to_continue(name, cfg, entries.get(pos))?;
+        source.set_entries(entries)?;
+        return persist_document(&source.path, &source.doc).await;
+    }
+    if let Some((i, pos)) = find(&sources, name, !disabled) {
+        let previous = sources[i].entries().remove(pos);
+        let entry = common_to_continue(name, cfg, Some(&previous))?;
+        return move_entry(dir, &mut sources[i], pos, name, entry, disabled).await;
+    }
+    write_new_block(dir, name, common_to_continue(name, cfg, None)?, disabled).await
+}
+
+pub struct ContinueStore {
+    dir: PathBuf,
+}
+
+impl ContinueStore {
+    pub fn new(path: Option<&str>) -> Self {
+        Self {
+            dir: ClientConfig::new("continue", path).get_path().to_path_buf(),
+        }
+    }
+}
+
+#[async_trait]
+impl ServerStore for ContinueStore {
+    fn client(&self) -> &str {
+        "continue"
+    }
+
+    async fn read_servers(&self) -> Result<Map<String, Value>, String> {
+        Ok(collect(&load_sources(&self.dir).await?, false))
+    }
+
+    async fn list_disabled(&self) -> Result<Map<String, Value>, String> {
+        Ok(collect(&load_sources(&self.dir).await?, true))
+    }
+
+    async fn upsert(&self, name: &str, cfg: Value) -> Result<(), String> {
+        let _guard = CONTINUE_LOCK.lock().await;
+        upsert_in_state(&self.dir, name, &cfg, false).await
+    }
+
+    async fn remove(&self, name: &str) -> Result<(), String> {
+        let _guard = CONTINUE_LOCK.lock().await;
+        let mut sources = load_sources(&self.dir).await?;
+        let (i, pos) = find(&sources, name, false)
+            .or_else(|| find(&sources, name, true))
+            .ok_or_else(|| format!("Continue server '{}' not found", name))?;
+        remove_entry(&mut sources[i], pos).await.map(|_| ())
+    }
+
+    async fn set_enabled(&self, name: &str, enabled: bool) -> Result<(), String> {
+        let _guard = CONTINUE_LOCK.lock().await;
+        let mut sources = load_sources(&self.dir).await?;
+        if find(&sources, name, !enabled).is_some() {
+            return Ok(());
+        }
+        let (i, pos) = find(&sources, name, enabled)
+            .ok_or_else(|| format!("Continue server '{}' not found", name))?;
+        let entry = sources[i].entries().remove(pos);
+        move_entry(&self.dir, &mut sources[i], pos, name, entry, !enabled).await
+    }
+
+    async fn update_disabled(&self, name: &str, cfg: Value) -> Result<(), String> {
+        let _guard = CONTINUE_LOCK.lock().await;
+        upsert_in_state(&self.dir, name, &cfg, true).await
+    }
+
+    async fn replace_all(
+        &self,
+        active: &Map<String, Value>,
+        disabled: &Map<String, Value>,
+    ) -> Result<(), String> {
+        let _guard = CONTINUE_LOCK.lock().await;
+        let sources = load_sources(&self.dir).await?;
+
+        // Write every replacement first, so a failure leaves the current servers alone
+        let wanted = active
+            .iter()
+            .map(|(name, cfg)| (name, cfg, false))
+            .chain(disabled.iter().map(|(name, cfg)| (name, cfg, true)));
+        let mut staged = Vec::new();
+        for (i, (name, cfg, is_disabled)) in wanted.enumerate() {
+            match stage_block(&self.dir, i, name, cfg).await {
+                Ok(path) => staged.push((path, name, is_disabled)),
+                Err(e) => {
+                    for (path, _, _) in &staged {
+                        let _ = fs::remove_file(path).await;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        // Hub references (`uses:`) cannot come from another client, keep them
+        for mut source in sources {
+            let entries = source.entries();
+            let kept: Vec<Value> = entries
+                .iter()
+                .filter(|e| continue_to_common(e).is_none())
+                .cloned()
+                .collect();
+            if kept.len() == entries.len() {
+                continue;
+            }
+            if source.is_block && kept.is_empty() {
+                note_self_removal(&source.path);
+                fs::remove_file(&source.path)
+                    .await
+                    .map_err(|e| format!("Failed to remove {}: {}", source.path.display(), e))?;
+            } else {
+                source.set_entries(kept)?;
+                persist_document(&source.path, &source.doc).await?;
+            }
+        }
+
+        for (path, name, is_disabled) in staged {
+            let target = new_block_path(&self.dir, name, is_disabled);
+            note_self_rename(&path, &target);
+            fs::rename(&path, &target)
+                .await
+                .map_err(|e| format!("Failed to rename {}: {}", path.display(), e))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+// Tests for the Continue block-file backend
+use crate::continue_dev::{common_to_continue, continue_to_common, ContinueStore};
+use crate::store::ServerStore;
+use serde_json::json;
+
+#[test]
+fn test_http_headers_round_trip() {
+    let entry = json!({
+        "name": "api", "type": "streamable-http", "url": "https://mcp.example.com",
+        "requestOptions": {"headers": {"Authorization": "Bearer x"}, "timeout": 10}
+    });
+    let common = continue_to_common(&entry).unwrap();
+    assert_eq!(
+        common,
+        json!({"type": "http", "url": "https://mcp.example.com", "headers": {"Authorization": "Bearer x"}, "requestOptions": {"timeout": 10}})
+    );
+    assert_eq!(common_to_continue("api", &common, None).unwrap(), entry);
+    assert!(continue_to_common(&json!({"uses": "anthropic/github-mcp"})).is_none());
+}
+
+#[tokio::test]
+async fn test_disable_renames_block_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ContinueStore::new(Some(dir.path().to_str().unwrap()));
+    let servers_dir = dir.path().join(".continue/mcpServers");
+
+    store
+        .upsert(
+            "My Fetch",
+            json!({"command": "uvx", "args": ["mcp-server-fetch"]}),
+        )
+        .await
+        .unwrap();
+    assert!(servers_dir.join("my-fetch.yaml").is_file());
+
+    store.set_enabled("My Fetch", false).await.unwrap();
+    assert!(!servers_dir.join("my-fetch.yaml").exists());
+    assert!(servers_dir.join("my-fetch.yaml.disabled").is_file());
+    assert!(store.read_servers().await.unwrap().is_empty());
+    assert_eq!(
+        store.list_disabled().await.unwrap()["My Fetch"]["command"],
+        "uvx"
+    );
+
+    store.remove("My Fetch").await.unwrap();
+    assert!(std::fs::read_dir(&servers_dir).unwrap().next().is_none());
+}
+
+#[tokio::test]
+async fn test_replace_all_keeps_hub_blocks_and_stages_writes() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ContinueStore::new(Some(dir.path().to_str().unwrap()));
+    let servers_dir = dir.path().join(".continue/mcpServers");
+    std::fs::create_dir_all(&servers_dir).unwrap();
+    std::fs::write(
+        servers_dir.join("github.yaml"),
+        "name: GitHub\nversion: 0.0.1\nschema: v1\nmcpServers:\n  - name: github\n    command: npx\n",
+    )
+    .unwrap();
+    std::fs::write(
+        servers_dir.join("hub.yaml"),
+        "name: Hub\nversion: 0.0.1\nschema: v1\nmcpServers:\n  - uses: anthropic/github-mcp\n",
+    )
+    .unwrap();
+    std::fs::write(
+        servers_dir.join("mixed.yaml"),
+        "name: Mixed\nversion: 0.0.1\nschema: v1\nmcpServers:\n  - uses: acme/search\n  - name: old\n    command: old\n",
+    )
+    .unwrap();
+
+    // A server the common shape cannot express fails before anything is removed
+    let bad = json!({"fetch": {"command": "uvx"}, "zz-broken": {}});
+    assert!(store
+        .replace_all(bad.as_object().unwrap(), &Default::default())
+        .await
+        .is_err());
+    assert!(servers_dir.join("github.yaml").is_file());
+    assert_eq!(std::fs::read_dir(&servers_dir).unwrap().count(), 3);
+    assert_eq!(store.read_servers().await.unwrap().len(), 2);
+
+    let active = json!({"fetch": {"command": "uvx", "args": ["mcp-server-fetch"]}});
+    let disabled = json!({"github": {"command": "npx"}});
+    store
+        .replace_all(active.as_object().unwrap(), disabled.as_object().unwrap())
+        .await
+        .unwrap();
+
+    let mut files: Vec<String> = std::fs::read_dir(&servers_dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec![
+            "fetch.yaml",
+            "github.yaml.disabled",
+            "hub.yaml",
+            "mixed.yaml"
+        ]
+    );
+    let mixed = std::fs::read_to_string(servers_dir.join("mixed.yaml")).unwrap();
+    assert!(mixed.contains("acme/search"));
+    assert!(!mixed.contains("old"));
+    assert_eq!(
+        store
+            .read_servers()
+            .await
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        vec!["fetch"]
+    );
+    assert_eq!(
+        store.list_disabled().await.unwrap()["github"]["command"],
+        "npx"
+    );
+}
@@ -0,0 +1,185 @@
+use crate::client::{ClientConfig, KNOWN_CLIENTS};
+use crate::config::{get_config_path, CodexConfig};
+use crate::json_manager::utils::{get_key_by_client, is_per_server_disabled_client};
+use crate::store::{store_for, ServerStore};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiscoveredClient {
+    pub client: String,
+    /// Product variant for clients hosted in several editors, e.g. "Code - Insiders"
+    pub variant: Option<String>,
+    pub path: String,
+    pub server_count: usize,
+    pub disabled_count: usize,
+    /// Only set for Claude Code, whose servers live per project
+    pub project_count: Option<usize>,
+    pub parse_error: Option<String>,
+    /// RFC 3339 timestamp of the last modification
+    pub modified: Option<String>,
+}
+
+pub(crate) struct ServerCounts {
+    servers: usize,
+    disabled: usize,
+    projects: Option<usize>,
+}
+
+fn modified_at(path: &Path) -> Option<String> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(|t| DateTime::<Utc>::from(t).to_rfc3339())
+}
+
+fn read_json(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))
+}
+
+fn object_len(value: Option<&Value>) -> usize {
+    value.and_then(|v| v.as_object()).map_or(0, |obj| obj.len())
+}
+
+pub(crate) fn count_json_servers(client: &str, path: &Path) -> Result<ServerCounts, String> {
+    let json = read_json(path)?;
+    let servers = json.get(get_key_by_client(client));
+
+    if is_per_server_disabled_client(client) {
+        let disabled = servers.and_then(|v| v.as_object()).map_or(0, |obj| {
+            obj.values()
+                .filter(|s| s.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false))
+                .count()
+        });
+        return Ok(ServerCounts {
+            servers: object_len(servers) - disabled,
+            disabled,
+            projects: None,
+        });
+    }
+
+    Ok(ServerCounts {
+        servers: object_len(servers),
+        disabled: object_len(json.get("__disabled")),
+        projects: None,
+    })
+}
+
+pub(crate) fn count_codex_servers(path: &Path) -> Result<ServerCounts, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let config: CodexConfig =
+        toml::from_str(&content).map_err(|e| format!("Failed to parse config file: {}", e))?;
+    let enabled = config
+        .mcp_servers
+        .values()
+        .filter(|cfg| cfg.is_enabled())
+        .count();
+    Ok(ServerCounts {
+        servers: enabled,
+        disabled: config.mcp_servers.len() - enabled + config.disabled_mcp_servers.len(),
+        projects: None,
+    })
+}
+
+pub(crate) fn count_claude_code_servers(path: &Path) -> Result<ServerCounts, String> {
+    let json = read_json(path)?;
+    let projects = json.get("projects").and_then(|p| p.as_object());
+    // User-scope servers live at the top level, local-scope ones under each project
+    let servers = object_len(json.get("mcpServers"))
+        + projects.map_or(0, |p| {
+            p.values()
+                .map(|project| object_len(project.get("mcpServers")))
+                .sum()
+        });
+    Ok(ServerCounts {
+        servers,
+        disabled: 0,
+        projects: Some(projects.map_or(0, |p| p.len())),
+    })
+}
+
+pub(crate) async fn count_store_servers(store: &dyn ServerStore) -> Result<ServerCounts, String> {
+    Ok(ServerCounts {
+        servers: store.read_servers().await?.len(),
+        disabled: store.list_disabled().await?.len(),
+        projects: None,
+    })
+}
+
+pub(crate) fn discovered(
+    client: &str,
+    variant: Option<String>,
+    path: &Path,
+    counts: Result<ServerCounts, String>,
+) -> DiscoveredClient {
+    let (counts, parse_error) = match counts {
+        Ok(counts) => (counts, None),
+        Err(e) => (
+            ServerCounts {
+                servers: 0,
+                disabled: 0,
+                projects: None,
+            },
+            Some(e),
+        ),
+    };
+    DiscoveredClient {
+        client: client.to_string(),
+        variant,
+        path: path.to_string_lossy().to_string(),
+        server_count: counts.servers,
+        disabled_count: counts.disabled,
+        project_count: counts.projects,
+        parse_error,
+        modified: modified_at(path),
+    }
+}
+
+/// Check every known client location and report the configs that exist
+#[tauri::command]
+pub async fn discover_clients() -> Result<Vec<DiscoveredClient>, String> {
+    let mut found = Vec::new();
+
+    for client in KNOWN_CLIENTS {
+        for (variant, path) in ClientConfig::known_locations(client) {
+            if path.is_file() {
+                let counts = count_json_servers(client, &path);
+                found.push(discovered(client, variant, &path, counts));
+            }
+        }
+    }
+
+    let store_clients = ["zed", "goose", "continue"]
+        .into_iter()
+        .chain(crate::client_defs::defined_clients());
+    for client in store_clients {
+        for (variant, path) in ClientConfig::known_locations(client) {
+            if path.exists() {
+                let path_str = path.to_string_lossy().to_string();
+                let counts = match store_for(client, Some(&path_str)) {
+                    Some(store) => count_store_servers(store.as_ref()).await,
+                    None => continue,
+                };
+                found.push(discovered(client, variant, &path, counts));
+            }
+        }
+    }
+
+    let claude_code_path = crate::claude_code_commands::get_claude_config_path(None)?;
+    if claude_code_path.is_file() {
+        let counts = count_claude_code_servers(&claude_code_path);
+        found.push(discovered("claude_code", None, &claude_code_path, counts));
+    }
+
+    let codex_path = get_config_path()?;
+    if codex_path.is_file() {
+        let counts = count_codex_servers(&codex_path);
+        found.push(discovered("codex", None, &codex_path, counts));
+    }
+
+    Ok(found)
+}
@@ -0,0 +1,139 @@
+// Tests for client discovery counts over temp config files
+use crate::discovery::{
+    count_claude_code_servers, count_codex_servers, count_json_servers, count_store_servers,
+    discovered,
+};
+use crate::store::store_for;
+use std::fs;
+
+#[test]
+fn test_json_clients_count_active_and_disabled() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let claude = dir.path().join("claude_desktop_config.json");
+    fs::write(
+        &claude,
+        r#"{"mcpServers": {"a": {"command": "a"}, "b": {"command": "b"}},
+            "__disabled": {"c": {"command": "c"}}}"#,
+    )
+    .unwrap();
+    let found = discovered(
+        "claude",
+        None,
+        &claude,
+        count_json_servers("claude", &claude),
+    );
+    assert_eq!((found.server_count, found.disabled_count), (2, 1));
+    assert!(found.parse_error.is_none());
+    assert!(found.modified.is_some());
+
+    // Cline keeps disabled servers in place with `disabled: true`
+    let cline = dir.path().join("cline_mcp_settings.json");
+    fs::write(
+        &cline,
+        r#"{"mcpServers": {"a": {"command": "a"}, "b": {"command": "b", "disabled": true}}}"#,
+    )
+    .unwrap();
+    let found = discovered(
+        "cline",
+        Some("Code - Insiders".to_string()),
+        &cline,
+        count_json_servers("cline", &cline),
+    );
+    assert_eq!((found.server_count, found.disabled_count), (1, 1));
+    assert_eq!(found.variant.as_deref(), Some("Code - Insiders"));
+
+    let broken = dir.path().join("mcp.json");
+    fs::write(&broken, "{ not json").unwrap();
+    let found = discovered(
+        "cursor",
+        None,
+        &broken,
+        count_json_servers("cursor", &broken),
+    );
+    assert_eq!(found.server_count, 0);
+    assert!(found
+        .parse_error
+        .unwrap()
+        .starts_with("Failed to parse JSON"));
+}
+
+#[test]
+fn test_claude_code_counts_user_and_project_servers() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(".claude.json");
+    fs::write(
+        &path,
+        r#"{"mcpServers": {"user": {"command": "u"}},
+            "projects": {"/a": {"mcpServers": {"x": {"command": "x"}, "y": {"command": "y"}}},
+                         "/b": {}}}"#,
+    )
+    .unwrap();
+    let found = discovered("claude_code", None, &path, count_claude_code_servers(&path));
+    assert_eq!(found.server_count, 3);
+    assert_eq!(found.project_count, Some(2));
+}
+
+#[test]
+fn test_codex_counts_enabled_flag_and_disabled_table() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(
+        &path,
+        r#"
+[mcp_servers.fetch]
+type = "stdio"
+command = "uvx"
+args = ["mcp-server-fetch"]
+
+[mcp_servers.off]
+type = "http"
+url = "https://off"
+enabled = false
+
+[disabled_mcp_servers.parked]
+type = "stdio"
+command = "parked"
+args = []
+"#,
+    )
+    .unwrap();
+    let found = discovered("codex", None, &path, count_codex_servers(&path));
+    assert_eq!((found.server_count, found.disabled_count), (1, 2));
+}
+
+#[tokio::test]
+async fn test_store_clients_read_the_discovered_file() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let zed = dir.path().join("settings.json");
+    fs::write(
+        &zed,
+        r#"{
+  // comments are fine in Zed settings
+  "context_servers": {
+    "fetch": {"command": "uvx", "args": ["mcp-server-fetch"]},
+    "off": {"command": "off", "args": [], "enabled": false}
+  }
+}"#,
+    )
+    .unwrap();
+    let store = store_for("zed", zed.to_str()).unwrap();
+    let found = discovered("zed", None, &zed, count_store_servers(store.as_ref()).await);
+    assert_eq!((found.server_count, found.disabled_count), (1, 1));
+
+    let goose = dir.path().join("config.yaml");
+    fs::write(
+        &goose,
+        "extensions:\n  fetch:\n    enabled: true\n    name: fetch\n    type: stdio\n    cmd: uvx\n    args: [mcp-server-fetch]\n",
+    )
+    .unwrap();
+    let store = store_for("goose", goose.to_str()).unwrap();
+    let found = discovered(
+        "goose",
+        None,
+        &goose,
+        count_store_servers(store.as_ref()).await,
+    );
+    assert_eq!((found.server_count, found.disabled_count), (1, 0));
+}
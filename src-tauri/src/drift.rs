@@ -0,0 +1,291 @@
+use crate::adapter::ClientAdapter;
+use crate::claude_code_commands::{get_claude_config_path, set_user_server};
+use crate::client::ClientConfig;
+use crate::inventory::{
+    config_hash, read_canonical, read_client_servers, read_discovered_clients, CellState,
+    ClientServers, InventoryClient, CANONICAL_CLIENT,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// Written by `JsonManager` on every server it adds or updates
+const CREATOR_MARKER: &str = "mcp_linker";
+
+/// Keys mcp-linker adds for itself; they never go into another config
+const BOOKKEEPING_KEYS: [&str; 3] = ["_creator", "updated_at", "disabled"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DriftKind {
+    /// Present in both with different content
+    Modified,
+    /// In the canonical store, gone from a client that mcp-linker manages
+    Removed,
+    /// In a client only, not written by mcp-linker
+    Added,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DriftEntry {
+    pub client: String,
+    pub variant: Option<String>,
+    pub path: String,
+    pub name: String,
+    pub kind: DriftKind,
+    /// The client entry carries the `_creator` marker, i.e. mcp-linker wrote it
+    pub managed: bool,
+    pub disabled: bool,
+    pub client_hash: Option<String>,
+    pub canonical_hash: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DriftReport {
+    pub canonical_path: String,
+    pub entries: Vec<DriftEntry>,
+    /// Clients that could not be read and were not checked
+    pub unreadable: Vec<InventoryClient>,
+}
+
+fn is_managed(cfg: &Value) -> bool {
+    cfg.get("_creator").and_then(|c| c.as_str()) == Some(CREATOR_MARKER)
+}
+
+fn without_bookkeeping(cfg: &Value) -> Value {
+    let mut cfg = cfg.clone();
+    if let Some(obj) = cfg.as_object_mut() {
+        for key in BOOKKEEPING_KEYS {
+            obj.remove(key);
+        }
+    }
+    cfg
+}
+
+/// Compare every client with the canonical store. A client counts as linked once it
+/// holds an entry written by mcp-linker; only linked clients report removals.
+pub(crate) fn detect_drift(
+    clients: &[(InventoryClient, ClientServers)],
+    canonical: &ClientServers,
+) -> Vec<DriftEntry> {
+    let mut entries = Vec::new();
+    for (client, servers) in clients {
+        if client.client == CANONICAL_CLIENT || client.error.is_some() {
+            continue;
+        }
+        let entry = |name: &str, kind, managed, disabled, client_hash, canonical_hash| DriftEntry {
+            client: client.client.clone(),
+            variant: client.variant.clone(),
+            path: client.path.clone(),
+            name: name.to_string(),
+            kind,
+            managed,
+            disabled,
+            client_hash,
+            canonical_hash,
+        };
+
+        let mut names: Vec<&String> = servers
+            .active
+            .keys()
+            .chain(servers.disabled.keys())
+            .collect();
+        names.sort();
+        for name in names {
+            let Some((cfg, state)) = servers.get(name) else {
+                continue;
+            };
+            let client_hash = config_hash(cfg);
+            let disabled = state == CellState::Disabled;
+            match canonical.get(name) {
+                Some((canonical_cfg, _)) => {
+                    let canonical_hash = config_hash(canonical_cfg);
+                    if canonical_hash != client_hash {
+                        entries.push(entry(
+                            name,
+                            DriftKind::Modified,
+                            is_managed(cfg),
+                            disabled,
+                            Some(client_hash),
+                            Some(canonical_hash),
+                        ));
+                    }
+                }
+                None if !is_managed(cfg) => entries.push(entry(
+                    name,
+                    DriftKind::Added,
+                    false,
+                    disabled,
+                    Some(client_hash),
+                    None,
+                )),
+                // Installed by mcp-linker straight into this client, not drift
+                None => {}
+            }
+        }
+
+        let linked = servers
+            .active
+            .values()
+            .chain(servers.disabled.values())
+            .any(is_managed);
+        if linked {
+            let mut missing: Vec<&String> = canonical
+                .active
+                .keys()
+                .filter(|name| servers.get(name).is_none())
+                .collect();
+            missing.sort();
+            for name in missing {
+                let canonical_hash = canonical.get(name).map(|(cfg, _)| config_hash(cfg));
+                entries.push(entry(
+                    name,
+                    DriftKind::Removed,
+                    false,
+                    false,
+                    None,
+                    canonical_hash,
+                ));
+            }
+        }
+    }
+    entries
+}
+
+/// Check every discovered client against `~/.config/mcplinker/mcp.json`
+#[tauri::command]
+pub async fn detect_config_drift() -> Result<DriftReport, String> {
+    let canonical = read_canonical().await?;
+    let clients = read_discovered_clients().await?;
+    let entries = detect_drift(&clients, &canonical);
+    println!("[Drift] {} drifted entries", entries.len());
+
+    Ok(DriftReport {
+        canonical_path: ClientConfig::new(CANONICAL_CLIENT, None)
+            .get_path()
+            .to_string_lossy()
+            .to_string(),
+        entries,
+        unreadable: clients
+            .into_iter()
+            .filter(|(client, _)| client.error.is_some())
+            .map(|(client, _)| client)
+            .collect(),
+    })
+}
+
+/// What adopting or reapplying one server comes down to
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DriftFix {
+    Write(Value),
+    /// Write, keeping the server disabled where it already was
+    WriteDisabled(Value),
+    Remove {
+        disabled: bool,
+    },
+}
+
+/// Adopting makes the canonical store hold what the client holds: its config, or
+/// nothing when the client no longer has the server
+pub(crate) fn adopt_fix(
+    client: &ClientServers,
+    canonical: &ClientServers,
+    name: &str,
+) -> Result<DriftFix, String> {
+    match (client.get(name), canonical.get(name)) {
+        (Some((cfg, _)), _) => Ok(DriftFix::Write(without_bookkeeping(cfg))),
+        (None, Some((_, state))) => Ok(DriftFix::Remove {
+            disabled: state == CellState::Disabled,
+        }),
+        (None, None) => Err(format!(
+            "Server '{}' is neither in the client nor in the canonical config",
+            name
+        )),
+    }
+}
+
+/// Reapplying makes the client hold what the canonical store holds, keeping the
+/// server's enabled/disabled state in that client
+pub(crate) fn reapply_fix(
+    canonical: &ClientServers,
+    client: &ClientServers,
+    name: &str,
+) -> Result<DriftFix, String> {
+    match (canonical.get(name), client.get(name)) {
+        (Some((cfg, _)), Some((_, CellState::Disabled))) => {
+            Ok(DriftFix::WriteDisabled(without_bookkeeping(cfg)))
+        }
+        (Some((cfg, _)), _) => Ok(DriftFix::Write(without_bookkeeping(cfg))),
+        (None, Some((_, state))) => Ok(DriftFix::Remove {
+            disabled: state == CellState::Disabled,
+        }),
+        (None, None) => Err(format!(
+            "Server '{}' is neither in the canonical config nor in the client",
+            name
+        )),
+    }
+}
+
+async fn apply_fix(
+    client: &str,
+    path: Option<&str>,
+    name: String,
+    fix: DriftFix,
+) -> Result<Value, String> {
+    // Claude Code's own file is written under its lock, without our bookkeeping
+    if client == "claude_code" {
+        let config_path = match path {
+            Some(path) => PathBuf::from(path),
+            None => get_claude_config_path(None)?,
+        };
+        let server = match fix {
+            DriftFix::Write(cfg) | DriftFix::WriteDisabled(cfg) => Some(cfg),
+            DriftFix::Remove { .. } => None,
+        };
+        return set_user_server(&config_path, &name, server).await;
+    }
+
+    let adapter = ClientAdapter::new(client, path);
+    match fix {
+        DriftFix::Write(cfg) => adapter.update(name, cfg).await,
+        DriftFix::WriteDisabled(cfg) => adapter.update_disabled(name, cfg).await,
+        DriftFix::Remove { disabled } => {
+            // Removal only looks at enabled servers
+            if disabled {
+                adapter.enable(name.clone()).await?;
+            }
+            adapter.remove(name).await
+        }
+    }
+}
+
+/// Make the client's version of `name` the canonical one; a server the client no
+/// longer has is removed from the canonical store
+#[tauri::command]
+pub async fn adopt_into_canonical(
+    client: String,
+    path: Option<String>,
+    name: String,
+) -> Result<Value, String> {
+    let servers = read_client_servers(&client, path.as_deref()).await?;
+    let canonical = read_canonical().await?;
+    let fix = adopt_fix(&servers, &canonical, &name)?;
+    println!("[Drift] adopt {} from {} into canonical", name, client);
+    apply_fix(CANONICAL_CLIENT, None, name, fix).await
+}
+
+/// Overwrite the client's version of `name` with the canonical one, keeping its
+/// enabled/disabled state in that client; a server the canonical store does not have
+/// is removed from the client
+#[tauri::command]
+pub async fn reapply_canonical(
+    client: String,
+    path: Option<String>,
+    name: String,
+) -> Result<Value, String> {
+    let canonical = read_canonical().await?;
+    let servers = read_client_servers(&client, path.as_deref()).await?;
+    let fix = reapply_fix(&canonical, &servers, &name)?;
+    println!("[Drift] reapply canonical {} to {}", name, client);
+    apply_fix(&client, path.as_deref(), name, fix).await
+}
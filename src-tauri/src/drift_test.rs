@@ -0,0 +1,97 @@
+// Tests for drift detection against the canonical store
+use crate::drift::{adopt_fix, detect_drift, reapply_fix, DriftFix, DriftKind};
+use crate::inventory_test::{client, servers};
+use serde_json::json;
+
+#[test]
+fn test_detects_modified_added_and_removed() {
+    let canonical = servers(
+        json!({
+            "github": {"command": "npx", "env": {"TOKEN": "a"}},
+            "fetch": {"command": "uvx"}
+        }),
+        json!({}),
+    );
+    let linked = servers(
+        json!({
+            "github": {"command": "npx", "env": {"TOKEN": "b"}, "_creator": "mcp_linker"},
+            "local": {"command": "./run.sh"}
+        }),
+        json!({}),
+    );
+    let unlinked = servers(
+        json!({"github": {"command": "npx", "env": {"TOKEN": "a"}}}),
+        json!({}),
+    );
+
+    let entries = detect_drift(
+        &[(client("cursor"), linked), (client("windsurf"), unlinked)],
+        &canonical,
+    );
+    let summary: Vec<(&str, &str, DriftKind, bool)> = entries
+        .iter()
+        .map(|e| {
+            (
+                e.client.as_str(),
+                e.name.as_str(),
+                e.kind.clone(),
+                e.managed,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("cursor", "github", DriftKind::Modified, true),
+            ("cursor", "local", DriftKind::Added, false),
+            ("cursor", "fetch", DriftKind::Removed, false),
+        ]
+    );
+}
+
+#[test]
+fn test_adopt_and_reapply_follow_what_each_side_holds() {
+    let canonical = servers(
+        json!({
+            "github": {"command": "npx", "env": {"TOKEN": "a"}, "_creator": "mcp_linker"},
+            "fetch": {"command": "uvx"}
+        }),
+        json!({}),
+    );
+    let client = servers(
+        json!({
+            "github": {"command": "npx", "env": {"TOKEN": "b"}, "_creator": "mcp_linker"},
+            "local": {"command": "./run.sh"}
+        }),
+        json!({"old": {"command": "old"}}),
+    );
+
+    // Modified: the side being written gets the other side's config
+    assert_eq!(
+        adopt_fix(&client, &canonical, "github").unwrap(),
+        DriftFix::Write(json!({"command": "npx", "env": {"TOKEN": "b"}}))
+    );
+    assert_eq!(
+        reapply_fix(&canonical, &client, "github").unwrap(),
+        DriftFix::Write(json!({"command": "npx", "env": {"TOKEN": "a"}}))
+    );
+
+    // Removed: adopting deletes it from the canonical store
+    assert_eq!(
+        adopt_fix(&client, &canonical, "fetch").unwrap(),
+        DriftFix::Remove { disabled: false }
+    );
+
+    // Added: reapplying deletes it from the client, disabled or not
+    assert_eq!(
+        reapply_fix(&canonical, &client, "local").unwrap(),
+        DriftFix::Remove { disabled: false }
+    );
+    assert_eq!(
+        reapply_fix(&canonical, &client, "old").unwrap(),
+        DriftFix::Remove { disabled: true }
+    );
+
+    assert!(adopt_fix(&client, &canonical, "missing").is_err());
+    assert!(reapply_fix(&canonical, &client, "missing").is_err());
+}
@@ -1,7 +1,55 @@
+use crate::dxt_manifest::{validate_manifest, ManifestError};
+use crate::dxt_sources::{
+    cache_dir, collect_catalog, find_manifest, load_sources, manifest_key, ManifestOrigin,
+    SourceReport,
+};
+use crate::safe_zip::safe_component;
 use glob::glob;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::io::{Cursor, Read};
-use zip::ZipArchive;
+use std::path::Path;
+
+/// `author/name` -> `ManifestOrigin`, next to the saved manifests
+pub(crate) const ORIGINS_FILE: &str = "origins.json";
+
+/// A catalog manifest that failed validation
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InvalidManifest {
+    /// `author/name`, or `#<index>` in the catalog when those are missing or unusable
+    pub id: String,
+    /// Whether it was still saved locally; entries without a usable `author.name`/`name`
+    /// cannot be
+    pub saved: bool,
+    pub errors: Vec<ManifestError>,
+}
+
+pub(crate) async fn read_catalog_manifest(path: &Path) -> Result<serde_json::Value, String> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read manifest: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse manifest: {}", e))
+}
+
+/// `user/repo` of a catalog entry, refusing names that are not plain directory names
+pub(crate) fn catalog_key(user: &str, repo: &str) -> Result<String, String> {
+    for part in [user, repo] {
+        if safe_component(part).as_deref() != Some(part) {
+            return Err(format!("'{}' is not a valid catalog name", part));
+        }
+    }
+    Ok(format!("{}/{}", user, repo))
+}
+
+pub(crate) fn catalog_id(path: &Path) -> String {
+    let mut parts = path.iter().rev().skip(1).take(2).collect::<Vec<_>>();
+    parts.reverse();
+    parts
+        .iter()
+        .map(|p| p.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
 
 #[tauri::command]
 pub async fn load_manifests() -> Result<serde_json::Value, String> {
@@ -33,9 +81,13 @@ pub async fn load_manifests() -> Result<serde_json::Value, String> {
                     .to_string_lossy()
                     .to_string();
 
-                let content = tokio::fs::read_to_string(&path).await?;
-                let json: serde_json::Value = serde_json::from_str(&content)?;
-                manifests.insert(format!("{}/{}", user, repo), json);
+                match read_catalog_manifest(&path).await {
+                    Ok(json) => {
+                        manifests.insert(format!("{}/{}", user, repo), json);
+                    }
+                    // Reported by `check_catalog_manifests`; one broken file must not hide the rest
+                    Err(e) => println!("[Dxt] skip {}/{}: {}", user, repo, e),
+                }
             }
         }
 
@@ -45,9 +97,38 @@ pub async fn load_manifests() -> Result<serde_json::Value, String> {
     .map_err(|e: anyhow::Error| e.to_string())
 }
 
+/// Validate every manifest in the local catalog and list the ones that fail
+#[tauri::command]
+pub async fn check_catalog_manifests() -> Result<Vec<InvalidManifest>, String> {
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    let pattern = home.join(".config/finder/dxt/*/*/manifest.json");
+    let paths = glob(&pattern.to_string_lossy()).map_err(|e| e.to_string())?;
+
+    let mut invalid = Vec::new();
+    for path in paths.flatten() {
+        let errors = match read_catalog_manifest(&path).await {
+            Ok(json) => validate_manifest(&json),
+            Err(e) => vec![ManifestError {
+                pointer: String::new(),
+                message: e,
+            }],
+        };
+        if !errors.is_empty() {
+            invalid.push(InvalidManifest {
+                id: catalog_id(&path),
+                saved: true,
+                errors,
+            });
+        }
+    }
+    invalid.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(invalid)
+}
+
 #[tauri::command]
 pub async fn load_manifest(user: String, repo: String) -> Result<serde_json::Value, String> {
     async {
+        catalog_key(&user, &repo).map_err(anyhow::Error::msg)?;
         let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let manifest_path = home
             .join(".config/finder/dxt")
@@ -67,40 +148,30 @@ pub async fn load_manifest(user: String, repo: String) -> Result<serde_json::Val
     .map_err(|e: anyhow::Error| e.to_string())
 }
 
+/// Fetch one manifest from the highest-priority source that has it
 #[tauri::command]
 pub async fn fetch_and_save_manifest(user: &str, repo: &str) -> Result<(), String> {
-    async {
-        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
-        let dxt_path = home.join(".config/finder/dxt").join(user).join(repo);
-
-        // Create the directory if it doesn't exist
-        if !dxt_path.exists() {
-            fs::create_dir_all(&dxt_path)?;
-        }
-
-        // Construct the GitHub URL
-        let url = format!(
-            "https://raw.githubusercontent.com/awesome-claude-dxt/servers/main/{}/{}/manifest.json",
-            user, repo
-        );
-
-        // Download the manifest.json
-        let response = reqwest::get(&url).await?;
-        let content = response.text().await?;
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    let dxt_path = home.join(".config/finder/dxt");
+    let key = catalog_key(user, repo)?;
+    let (manifest, origin) = find_manifest(&load_sources()?, &cache_dir()?, &key).await?;
 
-        // Save the file
-        let manifest_path = dxt_path.join("manifest.json");
-        tokio::fs::write(manifest_path, content).await?;
+    let manifest_dir = dxt_path.join(user).join(repo);
+    fs::create_dir_all(&manifest_dir).map_err(|e| format!("Failed to create dir: {}", e))?;
+    let content = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    tokio::fs::write(manifest_dir.join("manifest.json"), content)
+        .await
+        .map_err(|e| format!("Failed to save manifest: {}", e))?;
 
-        Ok(())
-    }
-    .await
-    .map_err(|e: anyhow::Error| e.to_string())
+    let mut origins = read_origins(&dxt_path);
+    origins.insert(key, origin);
+    write_origins(&dxt_path, &origins)
 }
 
 #[tauri::command]
 pub async fn read_dxt_setting(user: String, repo: String) -> Result<serde_json::Value, String> {
     async {
+        catalog_key(&user, &repo).map_err(anyhow::Error::msg)?;
         let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let settings_dir = home.join(".config/finder/dxt-settings");
         tokio::fs::create_dir_all(&settings_dir).await?;
@@ -125,8 +196,10 @@ pub async fn save_dxt_setting(
     content: serde_json::Value,
 ) -> Result<(), String> {
     async {
+        catalog_key(&user, &repo).map_err(anyhow::Error::msg)?;
         let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let settings_dir = home.join(".config/finder/dxt-settings");
+        tokio::fs::create_dir_all(&settings_dir).await?;
         let settings_path = settings_dir.join(format!("{}.{}.json", &user, &repo));
         let content_string = serde_json::to_string_pretty(&content)?;
         tokio::fs::write(settings_path, content_string).await?;
@@ -136,66 +209,95 @@ pub async fn save_dxt_setting(
     .map_err(|e: anyhow::Error| e.to_string())
 }
 
+/// Result of refreshing the catalog from every source
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CatalogRefresh {
+    pub sources: Vec<SourceReport>,
+    pub invalid: Vec<InvalidManifest>,
+}
+
+fn read_origins(dxt_path: &Path) -> BTreeMap<String, ManifestOrigin> {
+    fs::read(dxt_path.join(ORIGINS_FILE))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn write_origins(
+    dxt_path: &Path,
+    origins: &BTreeMap<String, ManifestOrigin>,
+) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(origins).map_err(|e| e.to_string())?;
+    fs::write(dxt_path.join(ORIGINS_FILE), content)
+        .map_err(|e| format!("Failed to save {}: {}", ORIGINS_FILE, e))
+}
+
+/// Merge every catalog source into `~/.config/finder/dxt` and record where each
+/// manifest came from. Fails only when no source could be read.
 #[tauri::command]
-pub async fn download_and_extract_manifests() -> Result<(), String> {
-    async {
-        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
-        let dxt_base_path = home.join(".config/finder/dxt");
-        
-        // Create base directory if it doesn't exist
-        if !dxt_base_path.exists() {
-            fs::create_dir_all(&dxt_base_path)?;
-        }
+pub async fn refresh_dxt_catalog() -> Result<CatalogRefresh, String> {
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    let dxt_base_path = home.join(".config/finder/dxt");
+    fs::create_dir_all(&dxt_base_path).map_err(|e| format!("Failed to create dir: {}", e))?;
 
-        // Download the zip file
-        let url = "https://github.com/milisp/awesome-claude-dxt/releases/download/v1.0.0/manifests.json.zip";
-        let response = reqwest::get(url).await?;
-        
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!("Failed to download manifests zip: {}", response.status()));
-        }
+    let (catalog, sources) = collect_catalog(&load_sources()?, &cache_dir()?).await;
+    if sources.iter().all(|s| s.error.is_some()) {
+        let errors: Vec<String> = sources
+            .iter()
+            .map(|s| format!("{}: {}", s.id, s.error.as_deref().unwrap_or_default()))
+            .collect();
+        return Err(format!(
+            "No catalog source could be read; {}",
+            errors.join("; ")
+        ));
+    }
 
-        let zip_data = response.bytes().await?;
-        
-        // Extract the zip file
-        let reader = Cursor::new(zip_data);
-        let mut archive = ZipArchive::new(reader)?;
-
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i)?;
-            
-            // Look for manifests.json file in the zip
-            if file.name() == "manifests.json" || file.name().ends_with("/manifests.json") {
-                let mut contents = String::new();
-                file.read_to_string(&mut contents)?;
-                
-                // Parse the JSON array of manifests
-                let manifests: serde_json::Value = serde_json::from_str(&contents)?;
-                
-                if let Some(manifests_array) = manifests.as_array() {
-                    // Save each manifest to its own directory structure
-                    for manifest in manifests_array {
-                        if let (Some(name), Some(author)) = (
-                            manifest.get("name").and_then(|n| n.as_str()),
-                            manifest.get("author").and_then(|a| a.get("name")).and_then(|n| n.as_str())
-                        ) {
-                            let manifest_dir = dxt_base_path.join(author).join(name);
-                            fs::create_dir_all(&manifest_dir)?;
-                            
-                            let manifest_path = manifest_dir.join("manifest.json");
-                            let manifest_content = serde_json::to_string_pretty(manifest)?;
-                            tokio::fs::write(manifest_path, manifest_content).await?;
-                        }
-                    }
+    let mut invalid = Vec::new();
+    let mut origins = BTreeMap::new();
+    for (index, (manifest, origin)) in catalog.into_iter().enumerate() {
+        let mut errors = validate_manifest(&manifest);
+        let key = match manifest_key(&manifest) {
+            Ok(key) => Some(key),
+            // Missing names are already reported by validation; unusable ones are not
+            Err(e) => {
+                if !errors.iter().any(|known| known.pointer == e.pointer) {
+                    errors.push(e);
                 }
-                break; // Found and processed manifests.json, no need to continue
+                None
             }
+        };
+        if let Some(key) = &key {
+            let manifest_dir = dxt_base_path.join(key);
+            fs::create_dir_all(&manifest_dir)
+                .map_err(|e| format!("Failed to create dir: {}", e))?;
+            let content = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+            tokio::fs::write(manifest_dir.join("manifest.json"), content)
+                .await
+                .map_err(|e| format!("Failed to save manifest: {}", e))?;
+            origins.insert(key.clone(), origin);
+        }
+        if !errors.is_empty() {
+            invalid.push(InvalidManifest {
+                id: key.clone().unwrap_or_else(|| format!("#{}", index)),
+                saved: key.is_some(),
+                errors,
+            });
         }
+    }
+    write_origins(&dxt_base_path, &origins)?;
 
-        Ok(())
+    if !invalid.is_empty() {
+        println!(
+            "[Dxt] {} catalog manifests failed validation",
+            invalid.len()
+        );
     }
-    .await
-    .map_err(|e: anyhow::Error| e.to_string())
+    Ok(CatalogRefresh { sources, invalid })
+}
+
+#[tauri::command]
+pub async fn download_and_extract_manifests() -> Result<Vec<InvalidManifest>, String> {
+    Ok(refresh_dxt_catalog().await?.invalid)
 }
 
 #[tauri::command]
@@ -0,0 +1,406 @@
+use crate::adapter::ClientAdapter;
+use crate::dxt::{read_dxt_setting, save_dxt_setting};
+use crate::dxt_manifest::{format_errors, validate_manifest};
+use crate::dxt_resolve::resolve_dxt;
+use crate::dxt_sources::manifest_key;
+use crate::json_manager::JsonManager;
+use crate::safe_zip::{extract_all, open_archive, read_capped, BUNDLE_LIMITS};
+use dirs::home_dir;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use tokio::task;
+use zip::ZipArchive;
+
+// A `.dxt` bundle is a zip with `manifest.json` at its root next to the server code.
+// Installed bundles live in `~/.config/mcplinker/extensions/<id>/`; `installed.json`
+// in that dir records every installed extension, bundle or catalog, with its version,
+// source and the client configs it was registered in.
+
+const MANIFEST_FILE: &str = "manifest.json";
+const REGISTRY_FILE: &str = "installed.json";
+
+/// A client config an extension is registered in
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ExtensionTarget {
+    pub client: String,
+    pub path: Option<String>,
+}
+
+/// Where an installed extension came from, and so where its upgrades come from
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExtensionSource {
+    /// A local `.dxt`; upgrading needs the newer bundle
+    Bundle { path: String },
+    /// A catalog manifest, upgraded from the catalog
+    Catalog {
+        /// Catalog source it was taken from, per `origins.json`
+        source: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstalledExtension {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub display_name: Option<String>,
+    /// Install dir of a bundle; catalog extensions have none
+    pub dir: Option<String>,
+    /// Server entry written to every target, `${__dirname}` already resolved
+    pub server: Value,
+    pub targets: Vec<ExtensionTarget>,
+    #[serde(default)]
+    pub source: Option<ExtensionSource>,
+    /// `author/name` in the catalog, which also keys the saved dxt-settings
+    #[serde(default)]
+    pub catalog_key: Option<String>,
+    /// Manifest as installed, kept to migrate settings on upgrade
+    #[serde(default)]
+    pub manifest: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DxtBundleInfo {
+    pub id: String,
+    pub manifest: Value,
+    pub files: usize,
+}
+
+pub(crate) fn extensions_dir() -> Result<PathBuf, String> {
+    home_dir()
+        .map(|home| home.join(".config/mcplinker/extensions"))
+        .ok_or_else(|| "Cannot find home directory".to_string())
+}
+
+/// Directory and server name for an extension: the manifest name reduced to
+/// `[a-z0-9._-]`
+pub(crate) fn extension_id(name: &str) -> Result<String, String> {
+    let id: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let id = id.trim_matches(|c| c == '-' || c == '.').to_string();
+    if id.is_empty() {
+        return Err(format!(
+            "Extension name '{}' has no usable characters",
+            name
+        ));
+    }
+    Ok(id)
+}
+
+fn str_field<'a>(value: &'a Value, pointer: &str) -> Option<&'a str> {
+    value
+        .pointer(pointer)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+}
+
+/// The manifest must be valid and its `entry_point` one of the bundled files
+pub(crate) fn validate_bundle(manifest: &Value, files: &[String]) -> Result<(), String> {
+    let errors = validate_manifest(manifest);
+    if !errors.is_empty() {
+        return Err(format!("manifest.json: {}", format_errors(&errors)));
+    }
+    if let Some(entry_point) = str_field(manifest, "/server/entry_point") {
+        let entry_point = entry_point.trim_start_matches("./");
+        if !files.iter().any(|f| f == entry_point) {
+            return Err(format!(
+                "manifest.json: entry point `{}` is not in the bundle",
+                entry_point
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn open_bundle(bundle: &Path) -> Result<(ZipArchive<File>, Value), String> {
+    let file =
+        File::open(bundle).map_err(|e| format!("Failed to open {}: {}", bundle.display(), e))?;
+    let mut archive = open_archive(file, &BUNDLE_LIMITS)
+        .map_err(|e| format!("{} is not a valid .dxt: {}", bundle.display(), e))?;
+    let manifest: Value = {
+        let entry = archive
+            .by_name(MANIFEST_FILE)
+            .map_err(|_| format!("{} has no manifest.json at its root", bundle.display()))?;
+        let bytes = read_capped(entry, BUNDLE_LIMITS.max_entry_size, MANIFEST_FILE)?;
+        serde_json::from_slice(&bytes).map_err(|e| format!("Invalid manifest.json: {}", e))?
+    };
+    let files: Vec<String> = archive.file_names().map(str::to_string).collect();
+    validate_bundle(&manifest, &files)?;
+    Ok((archive, manifest))
+}
+
+/// Read and validate a bundle without installing it
+pub(crate) fn inspect_bundle(bundle: &Path) -> Result<DxtBundleInfo, String> {
+    let (archive, manifest) = open_bundle(bundle)?;
+    Ok(DxtBundleInfo {
+        id: extension_id(str_field(&manifest, "/name").unwrap_or_default())?,
+        files: archive.len(),
+        manifest,
+    })
+}
+
+/// A bundle is all or nothing: any entry `extract_all` refuses fails the install
+fn extract_entries(archive: &mut ZipArchive<File>, target: &Path) -> Result<(), String> {
+    let rejected = extract_all(archive, target, &BUNDLE_LIMITS)?;
+    if !rejected.is_empty() {
+        let entries: Vec<String> = rejected
+            .iter()
+            .map(|r| format!("{} ({})", r.name, r.reason))
+            .collect();
+        return Err(format!("Bundle has unsafe entries: {}", entries.join(", ")));
+    }
+    Ok(())
+}
+
+/// Extract `bundle` into `<extensions_dir>/<id>`, replacing an earlier install only
+/// once the new one is complete. Returns the id, manifest and install dir.
+pub(crate) fn extract_bundle(
+    bundle: &Path,
+    extensions_dir: &Path,
+) -> Result<(String, Value, PathBuf), String> {
+    let (mut archive, manifest) = open_bundle(bundle)?;
+    let id = extension_id(str_field(&manifest, "/name").unwrap_or_default())?;
+
+    let staging = extensions_dir.join(format!(".{}.partial", id));
+    let target = extensions_dir.join(&id);
+    if staging.exists() {
+        fs::remove_dir_all(&staging).map_err(|e| format!("Failed to clean up: {}", e))?;
+    }
+    fs::create_dir_all(&staging).map_err(|e| format!("Failed to create dir: {}", e))?;
+    if let Err(e) = extract_entries(&mut archive, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+    if target.exists() {
+        fs::remove_dir_all(&target)
+            .map_err(|e| format!("Failed to replace {}: {}", target.display(), e))?;
+    }
+    fs::rename(&staging, &target)
+        .map_err(|e| format!("Failed to move extension into place: {}", e))?;
+    Ok((id, manifest, target))
+}
+
+pub(crate) async fn load_registry(dir: &Path) -> Result<Map<String, Value>, String> {
+    let json = JsonManager::read_json_file(&dir.join(REGISTRY_FILE)).await?;
+    Ok(json.as_object().cloned().unwrap_or_default())
+}
+
+pub(crate) async fn save_registry(dir: &Path, registry: Map<String, Value>) -> Result<(), String> {
+    JsonManager::write_json_file(&dir.join(REGISTRY_FILE), &Value::Object(registry)).await
+}
+
+pub(crate) fn registered(registry: &Map<String, Value>, id: &str) -> Option<InstalledExtension> {
+    registry
+        .get(id)
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+#[tauri::command]
+pub async fn inspect_dxt_bundle(bundle_path: String) -> Result<DxtBundleInfo, String> {
+    task::spawn_blocking(move || inspect_bundle(Path::new(&bundle_path)))
+        .await
+        .map_err(|e| format!("Failed to read bundle: {}", e))?
+}
+
+/// Write `extension.server` to every target and record the extension, keeping targets
+/// from an earlier install. Fails listing the targets it could not update.
+pub(crate) async fn register_extension(
+    dir: &Path,
+    mut extension: InstalledExtension,
+    targets: Vec<ExtensionTarget>,
+) -> Result<InstalledExtension, String> {
+    let mut registry = load_registry(dir).await?;
+    let mut all_targets = registered(&registry, &extension.id)
+        .map(|ext| ext.targets)
+        .unwrap_or_default();
+    let mut failures = Vec::new();
+    for target in targets {
+        let adapter = ClientAdapter::new(&target.client, target.path.as_deref());
+        match adapter
+            .update(extension.id.clone(), extension.server.clone())
+            .await
+        {
+            Ok(_) if !all_targets.contains(&target) => all_targets.push(target),
+            Ok(_) => {}
+            Err(e) => failures.push(format!("{}: {}", target.client, e)),
+        }
+    }
+
+    extension.targets = all_targets;
+    registry.insert(
+        extension.id.clone(),
+        serde_json::to_value(&extension).map_err(|e| e.to_string())?,
+    );
+    save_registry(dir, registry).await?;
+
+    if !failures.is_empty() {
+        return Err(format!(
+            "Installed {} but could not register it in: {}",
+            extension.id,
+            failures.join("; ")
+        ));
+    }
+    Ok(extension)
+}
+
+/// Install a local `.dxt` and register it as a server in every target client.
+/// `settings` are the values for the manifest's `user_config`; without them the
+/// settings saved for its catalog entry are used.
+#[tauri::command]
+pub async fn install_dxt_bundle(
+    bundle_path: String,
+    targets: Vec<ExtensionTarget>,
+    settings: Option<Value>,
+) -> Result<InstalledExtension, String> {
+    let dir = extensions_dir()?;
+    let bundle = PathBuf::from(&bundle_path);
+    let info = task::spawn_blocking(move || inspect_bundle(&bundle))
+        .await
+        .map_err(|e| format!("Failed to read bundle: {}", e))??;
+    let catalog_key = manifest_key(&info.manifest).ok();
+    let settings = match settings {
+        Some(settings) => settings,
+        None => saved_settings(catalog_key.as_deref()).await,
+    };
+
+    // Resolve before extracting so missing settings leave an earlier install alone
+    let resolved = resolve_dxt(&info.manifest, &settings, Some(&dir.join(&info.id)));
+    let server = resolved.server.ok_or_else(|| {
+        format!(
+            "Cannot install {}: {}",
+            info.id,
+            format_errors(&resolved.errors)
+        )
+    })?;
+
+    let extract_dir = dir.clone();
+    let source_path = bundle_path.clone();
+    let (id, manifest, install_dir) =
+        task::spawn_blocking(move || extract_bundle(Path::new(&source_path), &extract_dir))
+            .await
+            .map_err(|e| format!("Failed to extract bundle: {}", e))??;
+    println!("[Dxt] installed {} into {}", id, install_dir.display());
+    save_settings(catalog_key.as_deref(), &settings).await?;
+
+    let installed = InstalledExtension {
+        id,
+        name: str_field(&manifest, "/name")
+            .unwrap_or_default()
+            .to_string(),
+        version: str_field(&manifest, "/version")
+            .unwrap_or_default()
+            .to_string(),
+        display_name: str_field(&manifest, "/display_name").map(str::to_string),
+        dir: Some(install_dir.to_string_lossy().to_string()),
+        server,
+        targets: Vec::new(),
+        source: Some(ExtensionSource::Bundle { path: bundle_path }),
+        catalog_key,
+        manifest: Some(manifest),
+    };
+    register_extension(&dir, installed, targets).await
+}
+
+/// Settings saved for catalog entry `key`, or `{}`
+pub(crate) async fn saved_settings(key: Option<&str>) -> Value {
+    let Some((user, repo)) = key.and_then(|key| key.split_once('/')) else {
+        return json!({});
+    };
+    read_dxt_setting(user.to_string(), repo.to_string())
+        .await
+        .unwrap_or_else(|_| json!({}))
+}
+
+/// Save non-empty settings for catalog entry `key`
+pub(crate) async fn save_settings(key: Option<&str>, settings: &Value) -> Result<(), String> {
+    let has_settings = settings.as_object().is_some_and(|s| !s.is_empty());
+    match key.and_then(|key| key.split_once('/')) {
+        Some((user, repo)) if has_settings => {
+            save_dxt_setting(user.to_string(), repo.to_string(), settings.clone()).await
+        }
+        _ => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub async fn list_installed_extensions() -> Result<Vec<InstalledExtension>, String> {
+    let registry = load_registry(&extensions_dir()?).await?;
+    Ok(registry
+        .keys()
+        .filter_map(|id| registered(&registry, id))
+        .collect())
+}
+
+/// Removal errors that only mean there is nothing left to remove
+fn already_removed(error: &str) -> bool {
+    error.contains("not found")
+}
+
+/// Remove the extension from every client it was registered in, then delete its files
+/// and its record. If a client cannot be updated, the files stay and the registry keeps
+/// that target, so nothing is left pointing at a deleted install.
+pub(crate) async fn unregister_extension(dir: &Path, id: &str) -> Result<(), String> {
+    let mut registry = load_registry(dir).await?;
+    let mut installed =
+        registered(&registry, id).ok_or_else(|| format!("Extension '{}' is not installed", id))?;
+
+    let mut remaining = Vec::new();
+    let mut failures = Vec::new();
+    for target in &installed.targets {
+        let adapter = ClientAdapter::new(&target.client, target.path.as_deref());
+        match adapter.remove(id.to_string()).await {
+            Ok(_) => {}
+            Err(e) if already_removed(&e) => {
+                println!("[Dxt] {} was already gone from {}", id, target.client);
+            }
+            Err(e) => {
+                failures.push(format!("{}: {}", target.client, e));
+                remaining.push(target.clone());
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        installed.targets = remaining;
+        registry.insert(
+            id.to_string(),
+            serde_json::to_value(&installed).map_err(|e| e.to_string())?,
+        );
+        save_registry(dir, registry).await?;
+        return Err(format!(
+            "Could not uninstall {} from: {}",
+            id,
+            failures.join("; ")
+        ));
+    }
+
+    let install_dir = dir.join(id);
+    // Catalog extensions have no files of their own
+    if installed.dir.is_some() && install_dir.exists() {
+        fs::remove_dir_all(&install_dir)
+            .map_err(|e| format!("Failed to remove {}: {}", install_dir.display(), e))?;
+    }
+    registry.remove(id);
+    save_registry(dir, registry).await?;
+    println!("[Dxt] uninstalled {}", id);
+    Ok(())
+}
+
+/// Uninstall an extension from every client and delete its files
+#[tauri::command]
+pub async fn uninstall_dxt_extension(id: String) -> Result<(), String> {
+    unregister_extension(&extensions_dir()?, &id).await
+}
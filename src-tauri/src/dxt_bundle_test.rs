@@ -0,0 +1,117 @@
+// Tests for installing local .dxt bundles
+use crate::dxt_bundle::{
+    extension_id, extract_bundle, inspect_bundle, load_registry, register_extension, registered,
+    unregister_extension, ExtensionTarget, InstalledExtension,
+};
+use crate::dxt_manifest_test::manifest_with;
+use crate::dxt_resolve::resolve_dxt;
+use serde_json::json;
+use std::io::Write;
+use std::path::Path;
+use tempfile::tempdir;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+fn write_bundle(path: &Path, files: &[(&str, String)]) {
+    let mut zip = ZipWriter::new(std::fs::File::create(path).unwrap());
+    for (name, content) in files {
+        zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+fn manifest() -> String {
+    manifest_with(json!({
+        "name": "Weather Tools",
+        "version": "1.2.0",
+        "description": "Forecasts for any city",
+        "server": {"mcp_config": {"env": {"DATA_DIR": "${__dirname}/data"}}}
+    }))
+    .to_string()
+}
+
+#[test]
+fn test_extract_and_resolve_dirname() {
+    let temp_dir = tempdir().unwrap();
+    let bundle = temp_dir.path().join("weather.dxt");
+    write_bundle(
+        &bundle,
+        &[
+            ("manifest.json", manifest()),
+            ("server/index.js", "console.log('hi')".to_string()),
+        ],
+    );
+    assert_eq!(inspect_bundle(&bundle).unwrap().id, "weather-tools");
+
+    let extensions = temp_dir.path().join("extensions");
+    let (id, manifest, dir) = extract_bundle(&bundle, &extensions).unwrap();
+    assert_eq!(id, "weather-tools");
+    assert!(dir.join("server/index.js").exists());
+    assert!(!extensions.join(".weather-tools.partial").exists());
+
+    let entry = resolve_dxt(&manifest, &json!({}), Some(&dir))
+        .server
+        .unwrap();
+    let dir = dir.to_string_lossy();
+    assert_eq!(entry["type"], "stdio");
+    assert_eq!(entry["args"][0], format!("{}/server/index.js", dir));
+    assert_eq!(entry["env"]["DATA_DIR"], format!("{}/data", dir));
+}
+
+#[test]
+fn test_rejects_invalid_bundles() {
+    let temp_dir = tempdir().unwrap();
+    let bundle = temp_dir.path().join("broken.dxt");
+    write_bundle(&bundle, &[("manifest.json", manifest())]);
+    let err = inspect_bundle(&bundle).unwrap_err();
+    assert!(err.contains("server/index.js"), "{}", err);
+
+    write_bundle(&bundle, &[("README.md", "no manifest".to_string())]);
+    assert!(inspect_bundle(&bundle).is_err());
+
+    assert_eq!(extension_id("../../etc").unwrap(), "etc");
+    assert!(extension_id("..").is_err());
+}
+
+#[tokio::test]
+async fn test_uninstall_keeps_targets_it_could_not_update() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path().join("extensions");
+    std::fs::create_dir_all(dir.join("weather/server")).unwrap();
+    let target = |file: &str| ExtensionTarget {
+        client: "cursor".to_string(),
+        path: Some(temp_dir.path().join(file).to_string_lossy().to_string()),
+    };
+    let extension = InstalledExtension {
+        id: "weather".to_string(),
+        name: "Weather".to_string(),
+        version: "1.0.0".to_string(),
+        display_name: None,
+        dir: Some(dir.join("weather").to_string_lossy().to_string()),
+        server: json!({"type": "stdio", "command": "node"}),
+        targets: Vec::new(),
+        source: None,
+        catalog_key: None,
+        manifest: None,
+    };
+    let targets = vec![target("good.json"), target("broken.json")];
+    register_extension(&dir, extension, targets).await.unwrap();
+    // Gone from this one already, which is fine
+    std::fs::write(temp_dir.path().join("good.json"), r#"{"mcpServers": {}}"#).unwrap();
+    std::fs::write(temp_dir.path().join("broken.json"), "{not json").unwrap();
+
+    let err = unregister_extension(&dir, "weather").await.unwrap_err();
+    assert!(err.contains("cursor"), "{}", err);
+    assert!(dir.join("weather/server").exists());
+    let registry = load_registry(&dir).await.unwrap();
+    assert_eq!(
+        registered(&registry, "weather").unwrap().targets,
+        vec![target("broken.json")]
+    );
+
+    std::fs::write(temp_dir.path().join("broken.json"), "{}").unwrap();
+    unregister_extension(&dir, "weather").await.unwrap();
+    assert!(!dir.join("weather").exists());
+    assert!(registered(&load_registry(&dir).await.unwrap(), "weather").is_none());
+}
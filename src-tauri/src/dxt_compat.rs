@@ -0,0 +1,243 @@
+use crate::dxt::{catalog_id, read_catalog_manifest};
+use crate::dxt_manifest::{format_errors, parse_manifest, DxtManifest, DxtServerType};
+use crate::dxt_resolve::current_platform;
+use crate::installer::check_command_exists;
+use glob::glob;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// Whether a DXT manifest can run on this machine: its `compatibility.platforms`
+// against the OS, and the runtimes it needs against what `--version` reports.
+// `compatibility.claude_desktop` is not checked; it is about a different client.
+
+/// Runtimes we look for, each with the commands that provide it
+const RUNTIMES: [(&str, &[&str]); 3] = [
+    ("node", &["node"]),
+    ("python", &["python3", "python"]),
+    ("uv", &["uv"]),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MissingRuntime {
+    pub runtime: String,
+    /// Range from `compatibility.runtimes`, if the manifest gave one
+    pub required: Option<String>,
+    /// Version found on this machine; `None` when the runtime is not installed
+    pub found: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CompatStatus {
+    Installable,
+    NeedsRuntime {
+        missing: Vec<MissingRuntime>,
+    },
+    UnsupportedPlatform {
+        platform: String,
+        supported: Vec<String>,
+    },
+}
+
+/// First version-looking token of `--version` output, e.g. `v20.11.0`,
+/// `Python 3.12.1` or `uv 0.4.18 (abc 2024-09-30)`
+pub(crate) fn parse_version(output: &str) -> Option<Version> {
+    output.split_whitespace().find_map(|token| {
+        let token = token.trim_start_matches('v');
+        if !token.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let mut parts = token
+            .split('.')
+            .map(|p| {
+                p.chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect::<String>()
+            })
+            .take(3)
+            .map(|p| p.parse::<u64>().ok());
+        let major = parts.next().flatten()?;
+        let minor = parts.next().flatten().unwrap_or(0);
+        let patch = parts.next().flatten().unwrap_or(0);
+        Some(Version::new(major, minor, patch))
+    })
+}
+
+/// npm-style range (`>=18`, `>=3.8 <4`, `^16 || ^18`) as alternatives the semver crate
+/// understands
+pub(crate) fn parse_range(range: &str) -> Result<Vec<VersionReq>, String> {
+    range
+        .split("||")
+        .map(|alternative| {
+            // `>= 18` is one comparator, `>=3.8 <4` two
+            let mut comparators: Vec<String> = Vec::new();
+            for token in alternative.split_whitespace() {
+                let operator_only = comparators
+                    .last()
+                    .is_some_and(|last| last.chars().all(|c| "<>=~^".contains(c)));
+                match comparators.last_mut() {
+                    Some(last) if operator_only => last.push_str(token),
+                    _ => comparators.push(token.to_string()),
+                }
+            }
+            let joined = comparators.join(", ");
+            if joined.is_empty() {
+                return Ok(VersionReq::STAR);
+            }
+            VersionReq::parse(&joined).map_err(|e| format!("Invalid range '{}': {}", range, e))
+        })
+        .collect()
+}
+
+/// Runtimes a manifest needs: its server type's plus everything in
+/// `compatibility.runtimes`, with the range if one was given
+fn required_runtimes(manifest: &DxtManifest) -> BTreeMap<String, Option<String>> {
+    let mut required = BTreeMap::new();
+    match manifest.server.server_type {
+        DxtServerType::Node => {
+            required.insert("node".to_string(), None);
+        }
+        DxtServerType::Python => {
+            required.insert("python".to_string(), None);
+        }
+        DxtServerType::Binary => {}
+    }
+    if let Some(compat) = &manifest.compatibility {
+        for (runtime, range) in &compat.runtimes {
+            required.insert(runtime.clone(), Some(range.clone()));
+        }
+    }
+    required
+}
+
+/// Compare a manifest against `platform` and the detected runtime versions
+pub(crate) fn check_compatibility(
+    manifest: &DxtManifest,
+    platform: &str,
+    runtimes: &BTreeMap<String, Option<Version>>,
+) -> CompatStatus {
+    let platforms = manifest
+        .compatibility
+        .as_ref()
+        .and_then(|c| c.platforms.clone())
+        .unwrap_or_default();
+    if !platforms.is_empty() && !platforms.iter().any(|p| p == platform) {
+        return CompatStatus::UnsupportedPlatform {
+            platform: platform.to_string(),
+            supported: platforms,
+        };
+    }
+
+    let mut missing = Vec::new();
+    for (runtime, range) in required_runtimes(manifest) {
+        let found = runtimes.get(&runtime).cloned().flatten();
+        let satisfied = match (&found, &range) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(version), Some(range)) => match parse_range(range) {
+                Ok(reqs) => reqs.iter().any(|req| req.matches(version)),
+                // A range we cannot read should not hide an extension that may well work
+                Err(e) => {
+                    println!("[DxtCompat] {}: {}", manifest.name, e);
+                    true
+                }
+            },
+        };
+        if !satisfied {
+            missing.push(MissingRuntime {
+                runtime,
+                required: range,
+                found: found.map(|v| v.to_string()),
+            });
+        }
+    }
+    if missing.is_empty() {
+        CompatStatus::Installable
+    } else {
+        CompatStatus::NeedsRuntime { missing }
+    }
+}
+
+async fn command_version(command: &str) -> Option<Version> {
+    if !check_command_exists(command.to_string())
+        .await
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let mut cmd = tokio::process::Command::new(command);
+    cmd.arg("--version");
+    #[cfg(windows)]
+    cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    let output = cmd.output().await.ok()?;
+    // Older pythons print their version to stderr
+    let text = format!(
+        "{} {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    parse_version(&text)
+}
+
+/// Installed version of every runtime we know about, `None` where it is missing
+pub(crate) async fn detect_runtimes() -> BTreeMap<String, Option<Version>> {
+    let detections = RUNTIMES.iter().map(|(runtime, commands)| async move {
+        for command in commands.iter() {
+            if let Some(version) = command_version(command).await {
+                return (runtime.to_string(), Some(version));
+            }
+        }
+        (runtime.to_string(), None)
+    });
+    futures::future::join_all(detections)
+        .await
+        .into_iter()
+        .collect()
+}
+
+#[tauri::command]
+pub async fn detect_dxt_runtimes() -> Result<BTreeMap<String, Option<String>>, String> {
+    Ok(detect_runtimes()
+        .await
+        .into_iter()
+        .map(|(runtime, version)| (runtime, version.map(|v| v.to_string())))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn check_dxt_compatibility(manifest: Value) -> Result<CompatStatus, String> {
+    let manifest = parse_manifest(&manifest)
+        .map_err(|errors| format!("Invalid manifest: {}", format_errors(&errors)))?;
+    let runtimes = detect_runtimes().await;
+    Ok(check_compatibility(
+        &manifest,
+        current_platform(),
+        &runtimes,
+    ))
+}
+
+/// Status of every valid manifest in the local catalog, keyed by `user/repo`.
+/// Invalid ones are left to `check_catalog_manifests`.
+#[tauri::command]
+pub async fn check_catalog_compatibility() -> Result<BTreeMap<String, CompatStatus>, String> {
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    let pattern = home.join(".config/finder/dxt/*/*/manifest.json");
+    let paths = glob(&pattern.to_string_lossy()).map_err(|e| e.to_string())?;
+
+    let runtimes = detect_runtimes().await;
+    let mut statuses = BTreeMap::new();
+    for path in paths.flatten() {
+        let Ok(json) = read_catalog_manifest(&path).await else {
+            continue;
+        };
+        if let Ok(manifest) = parse_manifest(&json) {
+            statuses.insert(
+                catalog_id(&path),
+                check_compatibility(&manifest, current_platform(), &runtimes),
+            );
+        }
+    }
+    Ok(statuses)
+}
@@ -0,0 +1,72 @@
+// Tests for DXT compatibility checks
+use crate::dxt_compat::{check_compatibility, parse_range, parse_version, CompatStatus};
+use crate::dxt_manifest::parse_manifest;
+use crate::dxt_manifest_test::manifest_with;
+use semver::Version;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+fn manifest() -> crate::dxt_manifest::DxtManifest {
+    parse_manifest(&manifest_with(json!({
+        "server": {
+            "type": "python",
+            "entry_point": "server/main.py",
+            "mcp_config": {"command": "python3", "args": ["${__dirname}/server/main.py"]}
+        },
+        "compatibility": {
+            "platforms": ["darwin", "linux"],
+            "runtimes": {"python": ">=3.10 <4", "uv": ">= 0.4"}
+        }
+    })))
+    .unwrap()
+}
+
+#[test]
+fn test_parses_versions_and_ranges() {
+    assert_eq!(parse_version("v20.11.0\n"), Some(Version::new(20, 11, 0)));
+    assert_eq!(parse_version("Python 3.12.1"), Some(Version::new(3, 12, 1)));
+    assert_eq!(
+        parse_version("uv 0.4.18 (7b55e9790 2024-10-01)"),
+        Some(Version::new(0, 4, 18))
+    );
+    assert_eq!(parse_version("command not found"), None);
+
+    let reqs = parse_range("^16 || >= 18.2").unwrap();
+    assert!(reqs.iter().any(|r| r.matches(&Version::new(16, 4, 0))));
+    assert!(reqs.iter().any(|r| r.matches(&Version::new(20, 0, 0))));
+    assert!(!reqs.iter().any(|r| r.matches(&Version::new(17, 0, 0))));
+}
+
+#[test]
+fn test_checks_platform_and_runtimes() {
+    let manifest = manifest();
+    let mut runtimes = BTreeMap::from([
+        ("python".to_string(), Some(Version::new(3, 12, 1))),
+        ("uv".to_string(), Some(Version::new(0, 4, 18))),
+    ]);
+    assert_eq!(
+        check_compatibility(&manifest, "linux", &runtimes),
+        CompatStatus::Installable
+    );
+    assert!(matches!(
+        check_compatibility(&manifest, "win32", &runtimes),
+        CompatStatus::UnsupportedPlatform { .. }
+    ));
+
+    runtimes.insert("python".to_string(), Some(Version::new(3, 9, 6)));
+    runtimes.insert("uv".to_string(), None);
+    let CompatStatus::NeedsRuntime { missing } =
+        check_compatibility(&manifest, "darwin", &runtimes)
+    else {
+        panic!("expected missing runtimes");
+    };
+    let missing: Vec<(String, Option<String>)> =
+        missing.into_iter().map(|m| (m.runtime, m.found)).collect();
+    assert_eq!(
+        missing,
+        vec![
+            ("python".to_string(), Some("3.9.6".to_string())),
+            ("uv".to_string(), None),
+        ]
+    );
+}
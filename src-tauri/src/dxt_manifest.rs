@@ -0,0 +1,426 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+// Typed view of a DXT `manifest.json`. `validate_manifest` walks the raw JSON first so
+// every problem is reported with the JSON pointer it sits at, which serde cannot do;
+// `parse_manifest` only deserializes manifests that passed.
+
+const SERVER_TYPES: [&str; 3] = ["node", "python", "binary"];
+const PLATFORMS: [&str; 3] = ["darwin", "win32", "linux"];
+const USER_CONFIG_TYPES: [&str; 5] = ["string", "number", "boolean", "directory", "file"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ManifestError {
+    /// JSON pointer into the manifest, `""` for the document itself
+    pub pointer: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DxtManifest {
+    pub dxt_version: String,
+    pub name: String,
+    pub display_name: Option<String>,
+    pub version: String,
+    pub description: String,
+    pub author: DxtAuthor,
+    pub server: DxtServer,
+    #[serde(default)]
+    pub user_config: BTreeMap<String, UserConfigOption>,
+    #[serde(default)]
+    pub tools: Vec<DxtTool>,
+    #[serde(default)]
+    pub prompts: Vec<DxtPrompt>,
+    pub compatibility: Option<DxtCompatibility>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DxtAuthor {
+    pub name: String,
+    pub email: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DxtServerType {
+    Node,
+    Python,
+    Binary,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DxtServer {
+    #[serde(rename = "type")]
+    pub server_type: DxtServerType,
+    pub entry_point: String,
+    pub mcp_config: McpConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct McpConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Keyed by `darwin`, `win32` or `linux`
+    #[serde(default)]
+    pub platform_overrides: BTreeMap<String, McpConfigOverride>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct McpConfigOverride {
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub env: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UserConfigType {
+    String,
+    Number,
+    Boolean,
+    Directory,
+    File,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserConfigOption {
+    #[serde(rename = "type")]
+    pub option_type: UserConfigType,
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub required: bool,
+    pub default: Option<Value>,
+    #[serde(default)]
+    pub multiple: bool,
+    #[serde(default)]
+    pub sensitive: bool,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DxtTool {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DxtPrompt {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DxtCompatibility {
+    pub claude_desktop: Option<String>,
+    pub platforms: Option<Vec<String>>,
+    #[serde(default)]
+    pub runtimes: BTreeMap<String, String>,
+}
+
+/// Escape one reference token (RFC 6901)
+fn token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+struct Validator {
+    errors: Vec<ManifestError>,
+}
+
+impl Validator {
+    fn error(&mut self, pointer: &str, message: impl Into<String>) {
+        self.errors.push(ManifestError {
+            pointer: pointer.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn object<'a>(
+        &mut self,
+        parent: &'a Map<String, Value>,
+        base: &str,
+        key: &str,
+        required: bool,
+    ) -> Option<&'a Map<String, Value>> {
+        let pointer = format!("{}/{}", base, token(key));
+        match parent.get(key) {
+            Some(Value::Object(map)) => Some(map),
+            Some(_) => {
+                self.error(&pointer, "must be an object");
+                None
+            }
+            None if required => {
+                self.error(&pointer, "is required");
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn string<'a>(
+        &mut self,
+        parent: &'a Map<String, Value>,
+        base: &str,
+        key: &str,
+        required: bool,
+    ) -> Option<&'a str> {
+        let pointer = format!("{}/{}", base, token(key));
+        match parent.get(key) {
+            Some(Value::String(s)) if required && s.trim().is_empty() => {
+                self.error(&pointer, "must not be empty");
+                None
+            }
+            Some(Value::String(s)) => Some(s),
+            Some(_) => {
+                self.error(&pointer, "must be a string");
+                None
+            }
+            None if required => {
+                self.error(&pointer, "is required");
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn one_of(&mut self, parent: &Map<String, Value>, base: &str, key: &str, allowed: &[&str]) {
+        let value = self.string(parent, base, key, true);
+        if value.is_some_and(|v| !allowed.contains(&v)) {
+            self.error(
+                &format!("{}/{}", base, token(key)),
+                format!("must be one of {}", allowed.join(", ")),
+            );
+        }
+    }
+
+    fn boolean(&mut self, parent: &Map<String, Value>, base: &str, key: &str) {
+        if parent.get(key).is_some_and(|v| !v.is_boolean()) {
+            self.error(&format!("{}/{}", base, token(key)), "must be a boolean");
+        }
+    }
+
+    fn number(&mut self, parent: &Map<String, Value>, base: &str, key: &str) {
+        if parent.get(key).is_some_and(|v| !v.is_number()) {
+            self.error(&format!("{}/{}", base, token(key)), "must be a number");
+        }
+    }
+
+    fn string_array(&mut self, parent: &Map<String, Value>, base: &str, key: &str) {
+        let pointer = format!("{}/{}", base, token(key));
+        match parent.get(key) {
+            None => {}
+            Some(Value::Array(items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    if !item.is_string() {
+                        self.error(&format!("{}/{}", pointer, i), "must be a string");
+                    }
+                }
+            }
+            Some(_) => self.error(&pointer, "must be an array of strings"),
+        }
+    }
+
+    fn string_map(&mut self, parent: &Map<String, Value>, base: &str, key: &str) {
+        let pointer = format!("{}/{}", base, token(key));
+        if let Some(map) = self.object(parent, base, key, false) {
+            for (name, value) in map {
+                if !value.is_string() {
+                    self.error(&format!("{}/{}", pointer, token(name)), "must be a string");
+                }
+            }
+        }
+    }
+
+    /// `command`/`args`/`env`, shared by `mcp_config` and its platform overrides
+    fn launch_config(&mut self, config: &Map<String, Value>, base: &str, command_required: bool) {
+        self.string(config, base, "command", command_required);
+        self.string_array(config, base, "args");
+        self.string_map(config, base, "env");
+    }
+
+    fn server(&mut self, root: &Map<String, Value>) {
+        let Some(server) = self.object(root, "", "server", true) else {
+            return;
+        };
+        self.one_of(server, "/server", "type", &SERVER_TYPES);
+        self.string(server, "/server", "entry_point", true);
+        let Some(config) = self.object(server, "/server", "mcp_config", true) else {
+            return;
+        };
+        self.launch_config(config, "/server/mcp_config", true);
+
+        let base = "/server/mcp_config/platform_overrides";
+        let Some(overrides) =
+            self.object(config, "/server/mcp_config", "platform_overrides", false)
+        else {
+            return;
+        };
+        for (platform, value) in overrides {
+            let pointer = format!("{}/{}", base, token(platform));
+            if !PLATFORMS.contains(&platform.as_str()) {
+                self.error(
+                    &pointer,
+                    format!("platform must be one of {}", PLATFORMS.join(", ")),
+                );
+            }
+            match value.as_object() {
+                Some(config) => self.launch_config(config, &pointer, false),
+                None => self.error(&pointer, "must be an object"),
+            }
+        }
+    }
+
+    fn user_config(&mut self, root: &Map<String, Value>) {
+        let Some(options) = self.object(root, "", "user_config", false) else {
+            return;
+        };
+        for (key, value) in options {
+            let base = format!("/user_config/{}", token(key));
+            let Some(option) = value.as_object() else {
+                self.error(&base, "must be an object");
+                continue;
+            };
+            self.one_of(option, &base, "type", &USER_CONFIG_TYPES);
+            self.string(option, &base, "title", true);
+            self.string(option, &base, "description", true);
+            for flag in ["required", "multiple", "sensitive"] {
+                self.boolean(option, &base, flag);
+            }
+            self.number(option, &base, "min");
+            self.number(option, &base, "max");
+
+            let (Some(kind), Some(default)) = (
+                option.get("type").and_then(|t| t.as_str()),
+                option.get("default"),
+            ) else {
+                continue;
+            };
+            let multiple = option.get("multiple").and_then(|m| m.as_bool()) == Some(true);
+            let fits = |v: &Value| match kind {
+                "number" => v.is_number(),
+                "boolean" => v.is_boolean(),
+                _ => v.is_string(),
+            };
+            let ok = match default {
+                Value::Array(items) if multiple => items.iter().all(fits),
+                other => fits(other),
+            };
+            if !ok {
+                self.error(
+                    &format!("{}/default", base),
+                    format!("must match type `{}`", kind),
+                );
+            }
+        }
+    }
+
+    fn named_list(&mut self, root: &Map<String, Value>, key: &str, text_required: bool) {
+        let base = format!("/{}", key);
+        let Some(value) = root.get(key) else {
+            return;
+        };
+        let Some(items) = value.as_array() else {
+            self.error(&base, "must be an array");
+            return;
+        };
+        for (i, item) in items.iter().enumerate() {
+            let pointer = format!("{}/{}", base, i);
+            let Some(item) = item.as_object() else {
+                self.error(&pointer, "must be an object");
+                continue;
+            };
+            self.string(item, &pointer, "name", true);
+            self.string(item, &pointer, "description", false);
+            if text_required {
+                self.string(item, &pointer, "text", true);
+                self.string_array(item, &pointer, "arguments");
+            }
+        }
+    }
+
+    fn compatibility(&mut self, root: &Map<String, Value>) {
+        let Some(compat) = self.object(root, "", "compatibility", false) else {
+            return;
+        };
+        self.string(compat, "/compatibility", "claude_desktop", false);
+        self.string_array(compat, "/compatibility", "platforms");
+        if let Some(Value::Array(platforms)) = compat.get("platforms") {
+            for (i, platform) in platforms.iter().enumerate() {
+                if platform.as_str().is_some_and(|p| !PLATFORMS.contains(&p)) {
+                    self.error(
+                        &format!("/compatibility/platforms/{}", i),
+                        format!("must be one of {}", PLATFORMS.join(", ")),
+                    );
+                }
+            }
+        }
+        self.string_map(compat, "/compatibility", "runtimes");
+    }
+}
+
+/// Every problem with a raw manifest; empty when it is valid
+pub fn validate_manifest(manifest: &Value) -> Vec<ManifestError> {
+    let mut v = Validator { errors: Vec::new() };
+    let Some(root) = manifest.as_object() else {
+        v.error("", "manifest must be a JSON object");
+        return v.errors;
+    };
+    for key in ["dxt_version", "name", "version", "description"] {
+        v.string(root, "", key, true);
+    }
+    v.string(root, "", "display_name", false);
+    if let Some(author) = v.object(root, "", "author", true) {
+        v.string(author, "/author", "name", true);
+    }
+    v.server(root);
+    v.user_config(root);
+    v.named_list(root, "tools", false);
+    v.named_list(root, "prompts", true);
+    v.compatibility(root);
+    v.errors
+}
+
+/// Validate and deserialize a manifest
+pub fn parse_manifest(manifest: &Value) -> Result<DxtManifest, Vec<ManifestError>> {
+    let errors = validate_manifest(manifest);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    serde_json::from_value(manifest.clone()).map_err(|e| {
+        vec![ManifestError {
+            pointer: String::new(),
+            message: e.to_string(),
+        }]
+    })
+}
+
+/// `"<pointer>: <message>"` lines, for places that can only show a string
+pub fn format_errors(errors: &[ManifestError]) -> String {
+    errors
+        .iter()
+        .map(|e| {
+            let pointer = if e.pointer.is_empty() {
+                "/"
+            } else {
+                &e.pointer
+            };
+            format!("{}: {}", pointer, e.message)
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Check a manifest from the UI, e.g. before saving an edited one
+#[tauri::command]
+pub fn validate_dxt_manifest(manifest: Value) -> Vec<ManifestError> {
+    validate_manifest(&manifest)
+}
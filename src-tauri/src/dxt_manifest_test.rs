@@ -0,0 +1,113 @@
+// Tests for DXT manifest validation
+use crate::dxt_manifest::{parse_manifest, validate_manifest, DxtServerType, UserConfigType};
+use serde_json::{json, Value};
+
+/// Objects merge key by key; anything else replaces what is there
+fn merge(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                merge(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, value) => *base = value,
+    }
+}
+
+/// A valid node manifest with `overrides` merged in; shared by the DXT tests
+pub(crate) fn manifest_with(overrides: Value) -> Value {
+    let mut manifest = json!({
+        "dxt_version": "0.1",
+        "name": "files",
+        "version": "0.3.0",
+        "description": "Read and search local files",
+        "author": {"name": "Jane Doe"},
+        "server": {
+            "type": "node",
+            "entry_point": "server/index.js",
+            "mcp_config": {
+                "command": "node",
+                "args": ["${__dirname}/server/index.js"]
+            }
+        }
+    });
+    merge(&mut manifest, overrides);
+    manifest
+}
+
+fn manifest() -> Value {
+    manifest_with(json!({
+        "server": {
+            "mcp_config": {
+                "args": ["${__dirname}/server/index.js", "${user_config.root}"],
+                "platform_overrides": {"win32": {"command": "node.exe"}}
+            }
+        },
+        "user_config": {
+            "root": {
+                "type": "directory",
+                "title": "Root",
+                "description": "Directory to expose",
+                "required": true
+            },
+            "limit": {
+                "type": "number",
+                "title": "Limit",
+                "description": "Max results",
+                "default": 50,
+                "min": 1
+            }
+        },
+        "tools": [{"name": "read_file"}],
+        "compatibility": {"platforms": ["darwin", "linux"], "runtimes": {"node": ">=18"}}
+    }))
+}
+
+#[test]
+fn test_valid_manifest_parses() {
+    let parsed = parse_manifest(&manifest()).unwrap();
+    assert_eq!(parsed.server.server_type, DxtServerType::Node);
+    assert_eq!(
+        parsed.user_config["root"].option_type,
+        UserConfigType::Directory
+    );
+    assert_eq!(
+        parsed.server.mcp_config.platform_overrides["win32"]
+            .command
+            .as_deref(),
+        Some("node.exe")
+    );
+}
+
+#[test]
+fn test_errors_carry_json_pointers() {
+    let mut bad = manifest();
+    bad["author"] = json!({});
+    bad["server"]["type"] = json!("ruby");
+    bad["server"]["mcp_config"]["args"][1] = json!(3);
+    bad["server"]["mcp_config"]["platform_overrides"]["amiga"] = json!({});
+    bad["user_config"]["limit"]["default"] = json!("fifty");
+    bad["user_config"]["a/b"] = json!("nope");
+    bad["tools"][0] = json!({"description": "no name"});
+    bad["compatibility"]["platforms"][1] = json!("beos");
+
+    let mut pointers: Vec<String> = validate_manifest(&bad)
+        .into_iter()
+        .map(|e| e.pointer)
+        .collect();
+    pointers.sort();
+    assert_eq!(
+        pointers,
+        vec![
+            "/author/name",
+            "/compatibility/platforms/1",
+            "/server/mcp_config/args/1",
+            "/server/mcp_config/platform_overrides/amiga",
+            "/server/type",
+            "/tools/0/name",
+            "/user_config/a~1b",
+            "/user_config/limit/default",
+        ]
+    );
+    assert!(parse_manifest(&bad).is_err());
+}
@@ -0,0 +1,317 @@
+use crate::dxt_manifest::{
+    format_errors, parse_manifest, DxtManifest, ManifestError, UserConfigOption, UserConfigType,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// Turns a manifest's `mcp_config` plus the user's saved settings into the server entry
+// a client gets. Settings are a flat `{ "<user_config key>": value }` object, as
+// written by `save_dxt_setting`.
+
+/// What sensitive values look like in anything handed back for display
+const MASK: &str = "********";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResolvedDxtConfig {
+    /// Ready for `add_mcp_server`; `None` while there are errors
+    pub server: Option<Value>,
+    /// Effective settings after defaults, with sensitive ones masked
+    pub settings: Map<String, Value>,
+    /// Pointers are into the settings object, e.g. `/api_key`
+    pub errors: Vec<ManifestError>,
+}
+
+/// `platform_overrides` key for the OS we run on
+pub(crate) fn current_platform() -> &'static str {
+    match std::env::consts::OS {
+        "macos" => "darwin",
+        "windows" => "win32",
+        _ => "linux",
+    }
+}
+
+fn coerce(option: &UserConfigOption, value: &Value) -> Result<Value, String> {
+    match (&option.option_type, value) {
+        (UserConfigType::Number, Value::Number(_)) => Ok(value.clone()),
+        // Form inputs often hand numbers and booleans over as text
+        (UserConfigType::Number, Value::String(s)) => {
+            let s = s.trim();
+            match (s.parse::<i64>(), s.parse::<f64>()) {
+                (Ok(n), _) => Ok(json!(n)),
+                (_, Ok(n)) => Ok(json!(n)),
+                _ => Err("must be a number".to_string()),
+            }
+        }
+        (UserConfigType::Number, _) => Err("must be a number".to_string()),
+        (UserConfigType::Boolean, Value::Bool(_)) => Ok(value.clone()),
+        (UserConfigType::Boolean, Value::String(s)) if s == "true" || s == "false" => {
+            Ok(json!(s == "true"))
+        }
+        (UserConfigType::Boolean, _) => Err("must be a boolean".to_string()),
+        (_, Value::String(_)) => Ok(value.clone()),
+        _ => Err("must be a string".to_string()),
+    }
+}
+
+fn check_range(option: &UserConfigOption, value: &Value) -> Result<(), String> {
+    let Some(n) = value.as_f64() else {
+        return Ok(());
+    };
+    if option.min.is_some_and(|min| n < min) {
+        return Err(format!(
+            "must be at least {}",
+            option.min.unwrap_or_default()
+        ));
+    }
+    if option.max.is_some_and(|max| n > max) {
+        return Err(format!(
+            "must be at most {}",
+            option.max.unwrap_or_default()
+        ));
+    }
+    Ok(())
+}
+
+fn resolve_option(
+    option: &UserConfigOption,
+    value: Option<&Value>,
+) -> Result<Option<Value>, String> {
+    let value = match value {
+        Some(Value::Null) | None => option.default.as_ref(),
+        Some(Value::String(s)) if s.is_empty() => option.default.as_ref(),
+        Some(value) => Some(value),
+    };
+    let Some(value) = value else {
+        return if option.required {
+            Err("is required".to_string())
+        } else {
+            Ok(None)
+        };
+    };
+
+    let resolved = match value {
+        Value::Array(items) if option.multiple => Value::Array(
+            items
+                .iter()
+                .map(|item| coerce(option, item))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Array(_) => return Err("does not accept multiple values".to_string()),
+        single => coerce(option, single)?,
+    };
+    match &resolved {
+        Value::Array(items) => items
+            .iter()
+            .try_for_each(|item| check_range(option, item))?,
+        single => check_range(option, single)?,
+    }
+    Ok(Some(resolved))
+}
+
+/// Settings checked against `user_config`, with defaults filled in
+pub(crate) fn resolve_settings(
+    manifest: &DxtManifest,
+    settings: &Value,
+) -> Result<BTreeMap<String, Value>, Vec<ManifestError>> {
+    let empty = Map::new();
+    let given = settings.as_object().unwrap_or(&empty);
+    let mut resolved = BTreeMap::new();
+    let mut errors = Vec::new();
+    for (key, option) in &manifest.user_config {
+        match resolve_option(option, given.get(key)) {
+            Ok(Some(value)) => {
+                resolved.insert(key.clone(), value);
+            }
+            Ok(None) => {}
+            Err(message) => errors.push(ManifestError {
+                pointer: format!("/{}", key.replace('~', "~0").replace('/', "~1")),
+                message,
+            }),
+        }
+    }
+    if errors.is_empty() {
+        Ok(resolved)
+    } else {
+        Err(errors)
+    }
+}
+
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Built-in variables; `${__dirname}` only once the extension has an install dir
+fn variables(dirname: Option<&Path>) -> Vec<(String, String)> {
+    let mut vars = vec![(
+        "pathSeparator".to_string(),
+        std::path::MAIN_SEPARATOR.to_string(),
+    )];
+    let dirs = [
+        ("HOME", dirs::home_dir()),
+        ("DESKTOP", dirs::desktop_dir()),
+        ("DOCUMENTS", dirs::document_dir()),
+        ("DOWNLOADS", dirs::download_dir()),
+        ("__dirname", dirname.map(Path::to_path_buf)),
+    ];
+    vars.extend(dirs.into_iter().filter_map(|(name, dir)| {
+        dir.map(|dir| (name.to_string(), dir.to_string_lossy().to_string()))
+    }));
+    vars
+}
+
+const USER_CONFIG_PREFIX: &str = "${user_config.";
+
+/// Blank out `${user_config.x}` left for optional settings with no value and no default
+fn strip_unset(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(USER_CONFIG_PREFIX) {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn substitute(text: &str, vars: &[(String, String)], settings: &BTreeMap<String, Value>) -> String {
+    let mut out = text.to_string();
+    for (name, value) in vars {
+        out = out.replace(&format!("${{{}}}", name), value);
+    }
+    for (key, value) in settings {
+        let text = match value {
+            Value::Array(items) => items.iter().map(as_text).collect::<Vec<_>>().join(" "),
+            single => as_text(single),
+        };
+        out = out.replace(&format!("${{user_config.{}}}", key), &text);
+    }
+    strip_unset(&out)
+}
+
+/// `mcp_config` for this platform with every variable substituted. An argument that is
+/// exactly `${user_config.x}` of a multi-value option expands into one argument each,
+/// and is dropped when the option has no value.
+pub(crate) fn resolve_server(
+    manifest: &DxtManifest,
+    settings: &BTreeMap<String, Value>,
+    dirname: Option<&Path>,
+) -> Value {
+    let base = &manifest.server.mcp_config;
+    let overrides = base.platform_overrides.get(current_platform());
+    let command = overrides
+        .and_then(|o| o.command.clone())
+        .unwrap_or_else(|| base.command.clone());
+    let args = overrides
+        .and_then(|o| o.args.clone())
+        .unwrap_or_else(|| base.args.clone());
+    let mut env = base.env.clone();
+    if let Some(extra) = overrides.and_then(|o| o.env.as_ref()) {
+        env.extend(extra.clone());
+    }
+
+    let vars = variables(dirname);
+    let mut resolved_args = Vec::new();
+    for arg in &args {
+        let whole = arg
+            .strip_prefix(USER_CONFIG_PREFIX)
+            .and_then(|rest| rest.strip_suffix('}'))
+            .map(|key| settings.get(key));
+        match whole {
+            Some(Some(Value::Array(items))) => resolved_args.extend(items.iter().map(as_text)),
+            Some(None) => {}
+            _ => resolved_args.push(substitute(arg, &vars, settings)),
+        }
+    }
+    let env: Map<String, Value> = env
+        .iter()
+        .map(|(k, v)| (k.clone(), json!(substitute(v, &vars, settings))))
+        .collect();
+
+    let mut server = json!({
+        "type": "stdio",
+        "command": substitute(&command, &vars, settings),
+    });
+    if !resolved_args.is_empty() {
+        server["args"] = json!(resolved_args);
+    }
+    if !env.is_empty() {
+        server["env"] = Value::Object(env);
+    }
+    server
+}
+
+/// Validate settings and resolve the server entry in one go
+pub(crate) fn resolve_dxt(
+    manifest: &Value,
+    settings: &Value,
+    dirname: Option<&Path>,
+) -> ResolvedDxtConfig {
+    let manifest = match parse_manifest(manifest) {
+        Ok(manifest) => manifest,
+        Err(errors) => {
+            return ResolvedDxtConfig {
+                server: None,
+                settings: Map::new(),
+                errors: vec![ManifestError {
+                    pointer: String::new(),
+                    message: format!("Invalid manifest: {}", format_errors(&errors)),
+                }],
+            }
+        }
+    };
+    let (server, resolved, errors) = match resolve_settings(&manifest, settings) {
+        Ok(resolved) => (
+            Some(resolve_server(&manifest, &resolved, dirname)),
+            resolved,
+            Vec::new(),
+        ),
+        Err(errors) => (None, BTreeMap::new(), errors),
+    };
+    let settings = resolved
+        .into_iter()
+        .map(|(key, value)| {
+            let sensitive = manifest.user_config.get(&key).is_some_and(|o| o.sensitive);
+            (key, if sensitive { json!(MASK) } else { value })
+        })
+        .collect();
+    ResolvedDxtConfig {
+        server,
+        settings,
+        errors,
+    }
+}
+
+/// Resolve a manifest against settings, e.g. a catalog entry with the form's values
+#[tauri::command]
+pub fn resolve_dxt_config(
+    manifest: Value,
+    settings: Option<Value>,
+    dirname: Option<String>,
+) -> ResolvedDxtConfig {
+    resolve_dxt(
+        &manifest,
+        &settings.unwrap_or_else(|| json!({})),
+        dirname.as_deref().map(Path::new),
+    )
+}
+
+/// Resolve a catalog manifest with the settings saved for it by `save_dxt_setting`
+#[tauri::command]
+pub async fn resolve_saved_dxt_config(
+    user: String,
+    repo: String,
+) -> Result<ResolvedDxtConfig, String> {
+    let manifest = crate::dxt::load_manifest(user.clone(), repo.clone()).await?;
+    let settings = crate::dxt::read_dxt_setting(user, repo)
+        .await
+        .unwrap_or_else(|_| json!({}));
+    Ok(resolve_dxt(&manifest, &settings, None))
+}
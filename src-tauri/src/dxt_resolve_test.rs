@@ -0,0 +1,108 @@
+// Tests for resolving DXT user_config into a server entry
+use crate::dxt_manifest_test::manifest_with;
+use crate::dxt_resolve::{current_platform, resolve_dxt};
+use serde_json::json;
+use std::path::Path;
+
+fn manifest() -> serde_json::Value {
+    manifest_with(json!({
+        "server": {
+            "mcp_config": {
+                "args": [
+                    "${__dirname}/server/index.js",
+                    "--limit=${user_config.limit}",
+                    "${user_config.roots}"
+                ],
+                "env": {"API_KEY": "${user_config.api_key}", "CACHE": "${HOME}/.cache"},
+                "platform_overrides": {
+                    current_platform(): {"env": {"PLATFORM": "yes"}}
+                }
+            }
+        },
+        "user_config": {
+            "api_key": {
+                "type": "string",
+                "title": "API key",
+                "description": "Key for the search API",
+                "required": true,
+                "sensitive": true
+            },
+            "limit": {
+                "type": "number",
+                "title": "Limit",
+                "description": "Max results",
+                "default": 50,
+                "min": 1,
+                "max": 100
+            },
+            "roots": {
+                "type": "directory",
+                "title": "Roots",
+                "description": "Directories to expose",
+                "multiple": true,
+                "required": true
+            }
+        }
+    }))
+}
+
+#[test]
+fn test_resolves_settings_into_server() {
+    let settings = json!({"api_key": "secret", "roots": ["/a", "/b"]});
+    let resolved = resolve_dxt(&manifest(), &settings, Some(Path::new("/ext/files")));
+    assert!(resolved.errors.is_empty(), "{:?}", resolved.errors);
+
+    let server = resolved.server.unwrap();
+    assert_eq!(
+        server["args"],
+        json!(["/ext/files/server/index.js", "--limit=50", "/a", "/b"])
+    );
+    assert_eq!(server["env"]["API_KEY"], "secret");
+    assert_eq!(server["env"]["PLATFORM"], "yes");
+    let home = dirs::home_dir().unwrap();
+    assert_eq!(
+        server["env"]["CACHE"],
+        format!("{}/.cache", home.to_string_lossy())
+    );
+
+    // Sensitive values never come back for display
+    assert_eq!(resolved.settings["api_key"], "********");
+    assert_eq!(resolved.settings["limit"], 50);
+}
+
+#[test]
+fn test_reports_invalid_settings() {
+    let settings = json!({"limit": "500", "roots": "/a"});
+    let resolved = resolve_dxt(&manifest(), &settings, None);
+    assert!(resolved.server.is_none());
+    let errors: Vec<(String, String)> = resolved
+        .errors
+        .into_iter()
+        .map(|e| (e.pointer, e.message))
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            ("/api_key".to_string(), "is required".to_string()),
+            ("/limit".to_string(), "must be at most 100".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_unset_optional_settings_leave_no_placeholder() {
+    let mut manifest = manifest();
+    manifest["user_config"]["tag"] = json!({
+        "type": "string",
+        "title": "Tag",
+        "description": "Optional tag"
+    });
+    let config = &mut manifest["server"]["mcp_config"];
+    config["args"] = json!(["${user_config.tag}", "--tag=${user_config.tag}", "--fixed"]);
+    config["env"]["TAG"] = json!("${user_config.tag}");
+
+    let settings = json!({"api_key": "secret", "roots": ["/a"]});
+    let server = resolve_dxt(&manifest, &settings, None).server.unwrap();
+    assert_eq!(server["args"], json!(["--tag=", "--fixed"]));
+    assert_eq!(server["env"]["TAG"], "");
+}
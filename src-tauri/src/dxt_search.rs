@@ -0,0 +1,388 @@
+use crate::dxt::catalog_id;
+use crate::dxt_compat::{check_compatibility, detect_runtimes, CompatStatus};
+use crate::dxt_manifest::parse_manifest;
+use crate::dxt_resolve::current_platform;
+use glob::glob;
+use once_cell::sync::Lazy;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+use tokio::sync::Mutex;
+
+// In-memory index over `~/.config/finder/dxt/*/*/manifest.json`. Every search first
+// syncs it with the catalog dir, re-reading only manifests whose size or mtime changed,
+// so a refresh from `refresh_dxt_catalog` shows up without rebuilding everything.
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+/// Field weights for full-text matches; an exact word counts double a prefix
+const NAME_WEIGHT: u32 = 10;
+const KEYWORD_WEIGHT: u32 = 5;
+const TOOL_WEIGHT: u32 = 3;
+const AUTHOR_WEIGHT: u32 = 3;
+const DESCRIPTION_WEIGHT: u32 = 1;
+
+static INDEX: Lazy<Mutex<CatalogIndex>> = Lazy::new(|| Mutex::new(CatalogIndex::default()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogSort {
+    /// Best match first; by name when there is no query
+    #[default]
+    Relevance,
+    Name,
+    Author,
+    /// Most recently changed in the catalog first
+    Recent,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CatalogQuery {
+    /// Words to match against name, display name, description, keywords, tools and
+    /// author; every word must match the start of some word
+    pub query: Option<String>,
+    pub server_type: Option<String>,
+    /// Entries without `compatibility.platforms` match every platform
+    pub platform: Option<String>,
+    pub has_tools: Option<bool>,
+    /// Whether `check_dxt_compatibility` would say `installable` here
+    pub compatible: Option<bool>,
+    pub sort: CatalogSort,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchHit {
+    /// `user/repo` in the catalog
+    pub id: String,
+    pub score: u32,
+    /// Only worked out when the query filters on it
+    pub compatibility: Option<CompatStatus>,
+    pub manifest: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchResults {
+    /// Matches before paging
+    pub total: usize,
+    pub offset: usize,
+    pub hits: Vec<SearchHit>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct IndexSync {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedManifest {
+    manifest: Value,
+    modified: Option<SystemTime>,
+    len: u64,
+    sort_name: String,
+    sort_author: String,
+    server_type: Option<String>,
+    platforms: Vec<String>,
+    has_tools: bool,
+    /// (weight, words) per searchable field
+    fields: Vec<(u32, Vec<String>)>,
+    /// Worked out on first use; stays `None` for manifests that fail validation
+    compatibility: Option<CompatStatus>,
+    compat_checked: bool,
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn str_at<'a>(manifest: &'a Value, pointer: &str) -> &'a str {
+    manifest
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+}
+
+/// Strings in an array of strings, or in the `name` of an array of objects
+fn strings_at(manifest: &Value, pointer: &str) -> Vec<String> {
+    manifest
+        .pointer(pointer)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().or_else(|| item.get("name")?.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl IndexedManifest {
+    fn new(manifest: Value, modified: Option<SystemTime>, len: u64) -> Self {
+        let name = str_at(&manifest, "/name");
+        let display_name = str_at(&manifest, "/display_name");
+        let author = str_at(&manifest, "/author/name");
+        let tools = strings_at(&manifest, "/tools");
+        let fields = vec![
+            (NAME_WEIGHT, words(&format!("{} {}", name, display_name))),
+            (
+                KEYWORD_WEIGHT,
+                words(&strings_at(&manifest, "/keywords").join(" ")),
+            ),
+            (TOOL_WEIGHT, words(&tools.join(" "))),
+            (AUTHOR_WEIGHT, words(author)),
+            (
+                DESCRIPTION_WEIGHT,
+                words(&format!(
+                    "{} {}",
+                    str_at(&manifest, "/description"),
+                    str_at(&manifest, "/long_description")
+                )),
+            ),
+        ];
+        IndexedManifest {
+            sort_name: if display_name.is_empty() {
+                name
+            } else {
+                display_name
+            }
+            .to_lowercase(),
+            sort_author: author.to_lowercase(),
+            server_type: manifest
+                .pointer("/server/type")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            platforms: strings_at(&manifest, "/compatibility/platforms"),
+            has_tools: !tools.is_empty(),
+            fields,
+            compatibility: None,
+            compat_checked: false,
+            manifest,
+            modified,
+            len,
+        }
+    }
+
+    /// Sum over query words of the best field match; `None` when a word matches nothing
+    fn score(&self, query: &[String]) -> Option<u32> {
+        query.iter().try_fold(0, |total, q| {
+            let best = self
+                .fields
+                .iter()
+                .filter_map(|(weight, words)| {
+                    if words.iter().any(|w| w == q) {
+                        Some(weight * 2)
+                    } else if words.iter().any(|w| w.starts_with(q.as_str())) {
+                        Some(*weight)
+                    } else {
+                        None
+                    }
+                })
+                .max()?;
+            Some(total + best)
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CatalogIndex {
+    entries: HashMap<String, IndexedManifest>,
+    runtimes: Option<BTreeMap<String, Option<Version>>>,
+}
+
+impl CatalogIndex {
+    /// Bring the index in line with the manifests under `base`
+    pub(crate) fn sync(&mut self, base: &Path) -> IndexSync {
+        let mut stats = IndexSync::default();
+        let pattern = base.join("*/*/manifest.json");
+        let mut seen = HashSet::new();
+        if let Ok(paths) = glob(&pattern.to_string_lossy()) {
+            for path in paths.flatten() {
+                let Ok(meta) = fs::metadata(&path) else {
+                    continue;
+                };
+                let id = catalog_id(&path);
+                let modified = meta.modified().ok();
+                seen.insert(id.clone());
+                let unchanged = self
+                    .entries
+                    .get(&id)
+                    .is_some_and(|e| e.modified == modified && e.len == meta.len());
+                if unchanged {
+                    continue;
+                }
+                let manifest = fs::read(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| {
+                        serde_json::from_slice::<Value>(&bytes).map_err(|e| e.to_string())
+                    });
+                match manifest {
+                    Ok(manifest) => {
+                        let entry = IndexedManifest::new(manifest, modified, meta.len());
+                        if self.entries.insert(id, entry).is_some() {
+                            stats.updated += 1;
+                        } else {
+                            stats.added += 1;
+                        }
+                    }
+                    Err(e) => {
+                        println!("[DxtSearch] skip {}: {}", id, e);
+                        if self.entries.remove(&id).is_some() {
+                            stats.removed += 1;
+                        }
+                    }
+                }
+            }
+        }
+        let before = self.entries.len();
+        self.entries.retain(|id, _| seen.contains(id));
+        stats.removed += before - self.entries.len();
+        stats.total = self.entries.len();
+        stats
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.runtimes = None;
+    }
+
+    fn is_installable(
+        entry: &mut IndexedManifest,
+        runtimes: &BTreeMap<String, Option<Version>>,
+    ) -> bool {
+        if !entry.compat_checked {
+            entry.compatibility = parse_manifest(&entry.manifest)
+                .ok()
+                .map(|manifest| check_compatibility(&manifest, current_platform(), runtimes));
+            entry.compat_checked = true;
+        }
+        entry.compatibility == Some(CompatStatus::Installable)
+    }
+
+    /// Filter, rank and page the index. `runtimes` is only needed for the
+    /// `compatible` filter.
+    pub(crate) fn search(
+        &mut self,
+        query: &CatalogQuery,
+        runtimes: &BTreeMap<String, Option<Version>>,
+    ) -> SearchResults {
+        let terms = words(query.query.as_deref().unwrap_or_default());
+        let mut hits: Vec<(&String, &IndexedManifest, u32)> = Vec::new();
+        for (id, entry) in self.entries.iter_mut() {
+            if query
+                .server_type
+                .as_ref()
+                .is_some_and(|t| entry.server_type.as_ref() != Some(t))
+            {
+                continue;
+            }
+            if query
+                .platform
+                .as_ref()
+                .is_some_and(|p| !entry.platforms.is_empty() && !entry.platforms.contains(p))
+            {
+                continue;
+            }
+            if query.has_tools.is_some_and(|t| entry.has_tools != t) {
+                continue;
+            }
+            if query
+                .compatible
+                .is_some_and(|c| Self::is_installable(entry, runtimes) != c)
+            {
+                continue;
+            }
+            let Some(score) = entry.score(&terms) else {
+                continue;
+            };
+            hits.push((id, entry, score));
+        }
+
+        let by_name = |a: &IndexedManifest, b: &IndexedManifest| a.sort_name.cmp(&b.sort_name);
+        hits.sort_by(|(a_id, a, a_score), (b_id, b, b_score)| {
+            let order = match query.sort {
+                CatalogSort::Relevance => b_score.cmp(a_score).then_with(|| by_name(a, b)),
+                CatalogSort::Name => by_name(a, b),
+                CatalogSort::Author => a
+                    .sort_author
+                    .cmp(&b.sort_author)
+                    .then_with(|| by_name(a, b)),
+                CatalogSort::Recent => b.modified.cmp(&a.modified),
+            };
+            order.then_with(|| a_id.cmp(b_id))
+        });
+
+        let total = hits.len();
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+        SearchResults {
+            total,
+            offset: query.offset,
+            hits: hits
+                .into_iter()
+                .skip(query.offset)
+                .take(limit)
+                .map(|(id, entry, score)| SearchHit {
+                    id: id.clone(),
+                    score,
+                    compatibility: query
+                        .compatible
+                        .is_some()
+                        .then(|| entry.compatibility.clone())
+                        .flatten(),
+                    manifest: entry.manifest.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn catalog_dir() -> Result<std::path::PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| home.join(".config/finder/dxt"))
+        .ok_or_else(|| "Cannot find home directory".to_string())
+}
+
+#[tauri::command]
+pub async fn search_dxt_catalog(query: CatalogQuery) -> Result<SearchResults, String> {
+    let base = catalog_dir()?;
+    let mut index = INDEX.lock().await;
+    let stats = index.sync(&base);
+    if stats.added + stats.updated + stats.removed > 0 {
+        println!(
+            "[DxtSearch] index synced: +{} ~{} -{} ({} total)",
+            stats.added, stats.updated, stats.removed, stats.total
+        );
+    }
+    let runtimes = match (&index.runtimes, query.compatible) {
+        (Some(runtimes), _) => runtimes.clone(),
+        (None, Some(_)) => {
+            let runtimes = detect_runtimes().await;
+            index.runtimes = Some(runtimes.clone());
+            runtimes
+        }
+        (None, None) => BTreeMap::new(),
+    };
+    Ok(index.search(&query, &runtimes))
+}
+
+/// Drop the index and the detected runtimes, e.g. after installing node or python
+#[tauri::command]
+pub async fn rebuild_dxt_index() -> Result<IndexSync, String> {
+    let base = catalog_dir()?;
+    let mut index = INDEX.lock().await;
+    index.clear();
+    Ok(index.sync(&base))
+}
@@ -0,0 +1,192 @@
+// Tests for the DXT catalog search index
+use crate::dxt_manifest_test::manifest_with;
+use crate::dxt_search::{CatalogIndex, CatalogQuery, CatalogSort, IndexSync};
+use semver::Version;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn write(base: &Path, author: &str, name: &str, manifest: serde_json::Value) {
+    let dir = base.join(author).join(name);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("manifest.json"), manifest.to_string()).unwrap();
+}
+
+fn manifest(
+    author: &str,
+    name: &str,
+    server_type: &str,
+    extra: serde_json::Value,
+) -> serde_json::Value {
+    let mut manifest = manifest_with(json!({
+        "name": name,
+        "version": "1.0.0",
+        "description": format!("{} extension", name),
+        "author": {"name": author},
+        "server": {
+            "type": server_type,
+            "entry_point": "server/index",
+            "mcp_config": {"command": server_type, "args": []}
+        }
+    }));
+    manifest
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    manifest
+}
+
+fn ids(index: &mut CatalogIndex, query: CatalogQuery) -> Vec<String> {
+    let runtimes = BTreeMap::from([("node".to_string(), Some(Version::new(20, 0, 0)))]);
+    index
+        .search(&query, &runtimes)
+        .hits
+        .into_iter()
+        .map(|hit| hit.id)
+        .collect()
+}
+
+#[test]
+fn test_search_filters_and_pages() {
+    let temp_dir = tempdir().unwrap();
+    let base = temp_dir.path();
+    write(
+        base,
+        "jane",
+        "files",
+        manifest(
+            "jane",
+            "files",
+            "node",
+            json!({"keywords": ["filesystem"], "tools": [{"name": "read_file"}]}),
+        ),
+    );
+    write(
+        base,
+        "bob",
+        "weather",
+        manifest(
+            "bob",
+            "weather",
+            "python",
+            json!({
+                "description": "Forecasts, no files",
+                "compatibility": {"platforms": ["darwin"]}
+            }),
+        ),
+    );
+    write(
+        base,
+        "ann",
+        "grep",
+        manifest(
+            "ann",
+            "grep",
+            "node",
+            json!({"tools": [{"name": "search_files"}]}),
+        ),
+    );
+
+    let mut index = CatalogIndex::default();
+    let sync = index.sync(base);
+    assert_eq!((sync.added, sync.total), (3, 3));
+
+    // Name beats tool beats description; prefixes match
+    let query = |text: &str| CatalogQuery {
+        query: Some(text.to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        ids(&mut index, query("file")),
+        vec!["jane/files", "ann/grep", "bob/weather"]
+    );
+    assert_eq!(ids(&mut index, query("files jane")), vec!["jane/files"]);
+
+    let filtered = CatalogQuery {
+        server_type: Some("node".to_string()),
+        has_tools: Some(true),
+        sort: CatalogSort::Name,
+        ..Default::default()
+    };
+    assert_eq!(ids(&mut index, filtered), vec!["jane/files", "ann/grep"]);
+    let linux = CatalogQuery {
+        platform: Some("linux".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(ids(&mut index, linux).len(), 2);
+    let compatible = CatalogQuery {
+        compatible: Some(true),
+        sort: CatalogSort::Author,
+        ..Default::default()
+    };
+    assert_eq!(ids(&mut index, compatible), vec!["ann/grep", "jane/files"]);
+
+    let page = CatalogQuery {
+        sort: CatalogSort::Name,
+        offset: 1,
+        limit: Some(1),
+        ..Default::default()
+    };
+    let results = index.search(&page, &BTreeMap::new());
+    assert_eq!(results.total, 3);
+    assert_eq!(results.hits[0].id, "ann/grep");
+}
+
+#[test]
+fn test_sync_is_incremental() {
+    let temp_dir = tempdir().unwrap();
+    let base = temp_dir.path();
+    write(
+        base,
+        "jane",
+        "files",
+        manifest("jane", "files", "node", json!({})),
+    );
+    write(
+        base,
+        "bob",
+        "weather",
+        manifest("bob", "weather", "python", json!({})),
+    );
+
+    let mut index = CatalogIndex::default();
+    index.sync(base);
+    assert_eq!(
+        index.sync(base),
+        IndexSync {
+            added: 0,
+            updated: 0,
+            removed: 0,
+            total: 2
+        }
+    );
+
+    write(
+        base,
+        "jane",
+        "files",
+        manifest("jane", "files", "node", json!({"keywords": ["disk"]})),
+    );
+    std::fs::remove_dir_all(base.join("bob")).unwrap();
+    write(
+        base,
+        "ann",
+        "grep",
+        manifest("ann", "grep", "node", json!({})),
+    );
+    assert_eq!(
+        index.sync(base),
+        IndexSync {
+            added: 1,
+            updated: 1,
+            removed: 1,
+            total: 2
+        }
+    );
+    let disk = CatalogQuery {
+        query: Some("disk".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(ids(&mut index, disk), vec!["jane/files"]);
+}
@@ -0,0 +1,438 @@
+use crate::dxt_manifest::ManifestError;
+use crate::safe_zip::{open_archive, read_capped, read_entry, safe_component, CATALOG_LIMITS};
+use dirs::home_dir;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Where the DXT catalog comes from. Sources are listed in
+// `~/.config/mcplinker/dxt-sources.json`; each is a URL, a local directory or a local
+// zip. Downloads are cached in `~/.config/mcplinker/dxt-cache/<source id>/` with their
+// ETag/Last-Modified, so refreshes are conditional and work offline from the last
+// good copy.
+
+const SOURCES_FILE: &str = "dxt-sources.json";
+const CACHE_BODY: &str = "body";
+const CACHE_META: &str = "meta.json";
+const CATALOG_FILE: &str = "manifests.json";
+const DEFAULT_SOURCE_URL: &str =
+    "https://github.com/milisp/awesome-claude-dxt/releases/latest/download/manifests.json.zip";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    /// `manifests.json`, a zip containing it, or a single manifest, over HTTP(S)
+    Url,
+    /// A directory of `<author>/<name>/manifest.json`, or with `manifests.json` at its root
+    Directory,
+    /// A local zip containing `manifests.json`
+    Zip,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CatalogSource {
+    /// Also the cache directory name, so limited to `[A-Za-z0-9._-]`
+    pub id: String,
+    pub kind: SourceKind,
+    pub location: String,
+    /// Lower numbers win when two sources have the same `author/name`
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FetchState {
+    Downloaded,
+    /// The server answered 304; the cached copy is current
+    NotModified,
+    /// The download failed; the last good copy was used
+    Offline,
+    Local,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SourceReport {
+    pub id: String,
+    pub state: Option<FetchState>,
+    pub manifests: usize,
+    pub error: Option<String>,
+}
+
+/// Which source a catalog manifest was taken from
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ManifestOrigin {
+    pub source: String,
+    pub location: String,
+    pub state: FetchState,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct CacheMeta {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+fn config_dir() -> Result<PathBuf, String> {
+    home_dir()
+        .map(|home| home.join(".config/mcplinker"))
+        .ok_or_else(|| "Cannot find home directory".to_string())
+}
+
+pub(crate) fn cache_dir() -> Result<PathBuf, String> {
+    Ok(config_dir()?.join("dxt-cache"))
+}
+
+pub(crate) fn default_sources() -> Vec<CatalogSource> {
+    vec![CatalogSource {
+        id: "awesome-claude-dxt".to_string(),
+        kind: SourceKind::Url,
+        location: DEFAULT_SOURCE_URL.to_string(),
+        priority: 0,
+        enabled: true,
+    }]
+}
+
+fn check_sources(sources: &[CatalogSource]) -> Result<(), String> {
+    let mut seen = Vec::new();
+    for source in sources {
+        let usable = !source.id.is_empty()
+            && !source.id.starts_with('.')
+            && source
+                .id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !usable {
+            return Err(format!(
+                "Source id '{}' may only use letters, digits, '-', '_' and '.'",
+                source.id
+            ));
+        }
+        if seen.contains(&&source.id) {
+            return Err(format!("Source id '{}' is used twice", source.id));
+        }
+        if source.location.trim().is_empty() {
+            return Err(format!("Source '{}' has no location", source.id));
+        }
+        seen.push(&source.id);
+    }
+    Ok(())
+}
+
+/// Sources from `path` in priority order, or the defaults when there is no file
+pub(crate) fn load_sources_from(path: &Path) -> Result<Vec<CatalogSource>, String> {
+    if !path.exists() {
+        return Ok(default_sources());
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut sources: Vec<CatalogSource> =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+    check_sources(&sources)?;
+    sources.sort_by_key(|s| s.priority);
+    Ok(sources)
+}
+
+/// Manifests in a catalog file: a zip with `manifests.json`, a JSON array, or one
+/// manifest object
+pub(crate) fn manifests_from_bytes(bytes: &[u8]) -> Result<Vec<Value>, String> {
+    let json: Value = if bytes.starts_with(b"PK") {
+        let mut archive = open_archive(Cursor::new(bytes), &CATALOG_LIMITS)?;
+        let index = (0..archive.len())
+            .find(|&i| {
+                archive.name_for_index(i).is_some_and(|name| {
+                    name == CATALOG_FILE || name.ends_with(&format!("/{}", CATALOG_FILE))
+                })
+            })
+            .ok_or_else(|| format!("Zip has no {}", CATALOG_FILE))?;
+        let contents = read_entry(&mut archive, index, &CATALOG_LIMITS)?;
+        serde_json::from_slice(&contents).map_err(|e| format!("Invalid {}: {}", CATALOG_FILE, e))?
+    } else {
+        serde_json::from_slice(bytes).map_err(|e| format!("Invalid catalog JSON: {}", e))?
+    };
+    match json {
+        Value::Array(manifests) => Ok(manifests),
+        manifest @ Value::Object(_) => Ok(vec![manifest]),
+        _ => Err("Catalog must be a manifest or an array of them".to_string()),
+    }
+}
+
+/// A local catalog file, no larger than a downloaded one may be
+fn read_local(path: &Path) -> Result<Vec<u8>, String> {
+    let file =
+        fs::File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    read_capped(
+        file,
+        CATALOG_LIMITS.max_total_size,
+        &path.display().to_string(),
+    )
+}
+
+fn manifests_from_dir(dir: &Path) -> Result<Vec<Value>, String> {
+    let catalog = dir.join(CATALOG_FILE);
+    if catalog.exists() {
+        return manifests_from_bytes(&read_local(&catalog)?);
+    }
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+    let pattern = dir.join("*/*/manifest.json");
+    let paths = glob::glob(&pattern.to_string_lossy()).map_err(|e| e.to_string())?;
+    let mut manifests = Vec::new();
+    for path in paths.flatten() {
+        match read_local(&path)
+            .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|e| e.to_string()))
+        {
+            Ok(manifest) => manifests.push(manifest),
+            Err(e) => println!("[DxtSources] skip {}: {}", path.display(), e),
+        }
+    }
+    Ok(manifests)
+}
+
+/// GET `url`, conditional on what is cached in `cache`. Falls back to the cached body
+/// when the request fails or returns a catalog that does not parse.
+pub(crate) async fn fetch_cached(url: &str, cache: &Path) -> Result<(Vec<u8>, FetchState), String> {
+    let meta: CacheMeta = fs::read(cache.join(CACHE_META))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .filter(|meta: &CacheMeta| meta.url == url)
+        .unwrap_or_default();
+    let cached = fs::read(cache.join(CACHE_BODY))
+        .ok()
+        .filter(|_| meta.url == url);
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client.get(url);
+    if cached.is_some() {
+        if let Some(etag) = &meta.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &meta.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let downloaded = async {
+        let mut response = request.send().await.map_err(|e| e.to_string())?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let meta = CacheMeta {
+            url: url.to_string(),
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            body.extend_from_slice(&chunk);
+            if body.len() as u64 > CATALOG_LIMITS.max_total_size {
+                return Err(format!(
+                    "Catalog is larger than {} bytes",
+                    CATALOG_LIMITS.max_total_size
+                ));
+            }
+        }
+        Ok(Some((body, meta)))
+    }
+    .await;
+    // Only a body that parses replaces the last good copy; a broken one is a failed fetch
+    let downloaded = downloaded.and_then(|downloaded| match downloaded {
+        Some((body, meta)) => manifests_from_bytes(&body)
+            .map(|_| Some((body, meta)))
+            .map_err(|e| format!("unreadable catalog: {}", e)),
+        None => Ok(None),
+    });
+
+    match (downloaded, cached) {
+        (Ok(Some((body, meta))), _) => {
+            fs::create_dir_all(cache).map_err(|e| format!("Failed to create cache: {}", e))?;
+            fs::write(cache.join(CACHE_BODY), &body)
+                .map_err(|e| format!("Failed to write cache: {}", e))?;
+            let meta = serde_json::to_vec_pretty(&meta).map_err(|e| e.to_string())?;
+            fs::write(cache.join(CACHE_META), meta)
+                .map_err(|e| format!("Failed to write cache: {}", e))?;
+            Ok((body, FetchState::Downloaded))
+        }
+        (Ok(None), Some(body)) => Ok((body, FetchState::NotModified)),
+        (Ok(None), None) => Err(format!("{} answered 304 but nothing is cached", url)),
+        (Err(e), Some(body)) => {
+            println!("[DxtSources] {} failed ({}), using cached copy", url, e);
+            Ok((body, FetchState::Offline))
+        }
+        (Err(e), None) => Err(format!("Failed to download {}: {}", url, e)),
+    }
+}
+
+pub(crate) async fn load_source(
+    source: &CatalogSource,
+    cache_root: &Path,
+) -> Result<(Vec<Value>, FetchState), String> {
+    let location = Path::new(&source.location);
+    match source.kind {
+        SourceKind::Url => {
+            let (body, state) =
+                fetch_cached(&source.location, &cache_root.join(&source.id)).await?;
+            Ok((manifests_from_bytes(&body)?, state))
+        }
+        SourceKind::Directory => Ok((manifests_from_dir(location)?, FetchState::Local)),
+        SourceKind::Zip => Ok((
+            manifests_from_bytes(&read_local(location)?)?,
+            FetchState::Local,
+        )),
+    }
+}
+
+/// `author/name` of a manifest, each reduced to a safe directory name
+pub(crate) fn manifest_key(manifest: &Value) -> Result<String, ManifestError> {
+    let part = |pointer: &str| {
+        let value = manifest
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .ok_or_else(|| ManifestError {
+                pointer: pointer.to_string(),
+                message: "is required".to_string(),
+            })?;
+        safe_component(value).ok_or_else(|| ManifestError {
+            pointer: pointer.to_string(),
+            message: "is not usable as a directory name".to_string(),
+        })
+    };
+    Ok(format!("{}/{}", part("/author/name")?, part("/name")?))
+}
+
+/// Every enabled source merged in priority order; the first source with a given
+/// `author/name` wins. Manifests without one are kept so they can be reported.
+pub(crate) async fn collect_catalog(
+    sources: &[CatalogSource],
+    cache_root: &Path,
+) -> (Vec<(Value, ManifestOrigin)>, Vec<SourceReport>) {
+    let mut catalog: Vec<(Value, ManifestOrigin)> = Vec::new();
+    let mut seen: Vec<String> = Vec::new();
+    let mut reports = Vec::new();
+    for source in sources.iter().filter(|s| s.enabled) {
+        match load_source(source, cache_root).await {
+            Ok((manifests, state)) => {
+                reports.push(SourceReport {
+                    id: source.id.clone(),
+                    state: Some(state),
+                    manifests: manifests.len(),
+                    error: None,
+                });
+                for manifest in manifests {
+                    if let Ok(key) = manifest_key(&manifest) {
+                        if seen.contains(&key) {
+                            continue;
+                        }
+                        seen.push(key);
+                    }
+                    let origin = ManifestOrigin {
+                        source: source.id.clone(),
+                        location: source.location.clone(),
+                        state,
+                    };
+                    catalog.push((manifest, origin));
+                }
+            }
+            Err(e) => {
+                println!("[DxtSources] {}: {}", source.id, e);
+                reports.push(SourceReport {
+                    id: source.id.clone(),
+                    state: None,
+                    manifests: 0,
+                    error: Some(e),
+                });
+            }
+        }
+    }
+    (catalog, reports)
+}
+
+/// The highest-priority source that has `key` (`author/name`)
+pub(crate) async fn find_manifest(
+    sources: &[CatalogSource],
+    cache_root: &Path,
+    key: &str,
+) -> Result<(Value, ManifestOrigin), String> {
+    let mut errors = Vec::new();
+    for source in sources.iter().filter(|s| s.enabled) {
+        match load_source(source, cache_root).await {
+            Ok((manifests, state)) => {
+                let found = manifests
+                    .into_iter()
+                    .find(|m| manifest_key(m).is_ok_and(|k| k == key));
+                if let Some(manifest) = found {
+                    let origin = ManifestOrigin {
+                        source: source.id.clone(),
+                        location: source.location.clone(),
+                        state,
+                    };
+                    return Ok((manifest, origin));
+                }
+            }
+            Err(e) => errors.push(format!("{}: {}", source.id, e)),
+        }
+    }
+    if errors.is_empty() {
+        Err(format!("No catalog source has {}", key))
+    } else {
+        Err(format!("{} not found; {}", key, errors.join("; ")))
+    }
+}
+
+pub(crate) fn load_sources() -> Result<Vec<CatalogSource>, String> {
+    load_sources_from(&config_dir()?.join(SOURCES_FILE))
+}
+
+#[tauri::command]
+pub fn get_dxt_sources() -> Result<Vec<CatalogSource>, String> {
+    load_sources()
+}
+
+#[tauri::command]
+pub fn save_dxt_sources(sources: Vec<CatalogSource>) -> Result<(), String> {
+    check_sources(&sources)?;
+    let dir = config_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create dir: {}", e))?;
+    let content = serde_json::to_string_pretty(&sources).map_err(|e| e.to_string())?;
+    fs::write(dir.join(SOURCES_FILE), content).map_err(|e| format!("Failed to save sources: {}", e))
+}
+
+/// `author/name` -> source, as recorded by the last catalog refresh
+#[tauri::command]
+pub fn get_manifest_origins() -> Result<BTreeMap<String, ManifestOrigin>, String> {
+    let home = home_dir().ok_or("Cannot find home directory")?;
+    let path = home
+        .join(".config/finder/dxt")
+        .join(crate::dxt::ORIGINS_FILE);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(BTreeMap::new());
+    };
+    serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+}
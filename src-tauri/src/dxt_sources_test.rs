@@ -0,0 +1,189 @@
+// Tests for DXT catalog sources, against a local HTTP stand-in
+use crate::dxt_sources::{collect_catalog, fetch_cached, CatalogSource, FetchState, SourceKind};
+use serde_json::json;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tempfile::tempdir;
+
+/// Serves `body` with an ETag, answering 304 when the request carries it.
+/// Returns the base URL and the conditional headers each request arrived with.
+fn serve(body: String, requests: usize) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/manifests.json", listener.local_addr().unwrap());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming().take(requests) {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+            let etag = request
+                .lines()
+                .find_map(|l| l.strip_prefix("if-none-match: "))
+                .map(|v| v.trim().to_string());
+            let response = if etag.as_deref() == Some("\"v1\"") {
+                "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nContent-Length: 0\r\n\r\n".to_string()
+            } else {
+                format!(
+                    "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            };
+            log.lock().unwrap().push(etag);
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (url, seen)
+}
+
+/// Answers each request with the next of `bodies`, unconditionally
+fn serve_each(bodies: Vec<String>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/manifests.json", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for (stream, body) in listener.incoming().zip(bodies) {
+            let mut stream = stream.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf).unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    url
+}
+
+fn manifest(author: &str, name: &str, version: &str) -> serde_json::Value {
+    json!({"name": name, "version": version, "author": {"name": author}})
+}
+
+#[tokio::test]
+async fn test_conditional_fetch_and_offline_fallback() {
+    let temp_dir = tempdir().unwrap();
+    let cache = temp_dir.path().join("cache");
+    let body = json!([manifest("jane", "files", "1.0.0")]).to_string();
+    let (url, seen) = serve(body.clone(), 2);
+
+    let (first, state) = fetch_cached(&url, &cache).await.unwrap();
+    assert_eq!(state, FetchState::Downloaded);
+    assert_eq!(first, body.as_bytes());
+
+    let (second, state) = fetch_cached(&url, &cache).await.unwrap();
+    assert_eq!(state, FetchState::NotModified);
+    assert_eq!(second, first);
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![None, Some("\"v1\"".to_string())]
+    );
+
+    // The stand-in has stopped answering; the last good copy is used
+    let (offline, state) = fetch_cached(&url, &cache).await.unwrap();
+    assert_eq!(state, FetchState::Offline);
+    assert_eq!(offline, first);
+}
+
+#[tokio::test]
+async fn test_broken_download_falls_back_to_cached_copy() {
+    let temp_dir = tempdir().unwrap();
+    let cache = temp_dir.path().join("cache");
+    let body = json!([manifest("jane", "files", "1.0.0")]).to_string();
+    let truncated = body[..body.len() / 2].to_string();
+    let url = serve_each(vec![body.clone(), truncated.clone()]);
+
+    let (first, state) = fetch_cached(&url, &cache).await.unwrap();
+    assert_eq!(state, FetchState::Downloaded);
+
+    let (second, state) = fetch_cached(&url, &cache).await.unwrap();
+    assert_eq!(state, FetchState::Offline);
+    assert_eq!(second, first);
+
+    // Without a cached copy there is nothing to fall back to
+    let url = serve_each(vec![truncated]);
+    let empty = temp_dir.path().join("empty");
+    assert!(fetch_cached(&url, &empty).await.is_err());
+    assert!(!empty.exists());
+}
+
+#[tokio::test]
+async fn test_sources_merge_by_priority() {
+    let temp_dir = tempdir().unwrap();
+    let local = temp_dir.path().join("local");
+    std::fs::create_dir_all(local.join("jane/files")).unwrap();
+    std::fs::write(
+        local.join("jane/files/manifest.json"),
+        manifest("jane", "files", "2.0.0-local").to_string(),
+    )
+    .unwrap();
+    let body = json!([
+        manifest("jane", "files", "1.0.0"),
+        manifest("bob", "weather", "0.1.0"),
+    ])
+    .to_string();
+    let (url, _) = serve(body, 1);
+
+    let sources = vec![
+        CatalogSource {
+            id: "local".to_string(),
+            kind: SourceKind::Directory,
+            location: local.to_string_lossy().to_string(),
+            priority: 0,
+            enabled: true,
+        },
+        CatalogSource {
+            id: "remote".to_string(),
+            kind: SourceKind::Url,
+            location: url,
+            priority: 10,
+            enabled: true,
+        },
+        CatalogSource {
+            id: "missing".to_string(),
+            kind: SourceKind::Zip,
+            location: temp_dir
+                .path()
+                .join("nope.zip")
+                .to_string_lossy()
+                .to_string(),
+            priority: 20,
+            enabled: true,
+        },
+    ];
+    let (catalog, reports) = collect_catalog(&sources, &temp_dir.path().join("cache")).await;
+
+    let mut entries: Vec<(String, String, String)> = catalog
+        .into_iter()
+        .map(|(m, origin)| {
+            (
+                m["name"].as_str().unwrap().to_string(),
+                m["version"].as_str().unwrap().to_string(),
+                origin.source,
+            )
+        })
+        .collect();
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            (
+                "files".to_string(),
+                "2.0.0-local".to_string(),
+                "local".to_string()
+            ),
+            (
+                "weather".to_string(),
+                "0.1.0".to_string(),
+                "remote".to_string()
+            ),
+        ]
+    );
+    assert_eq!(reports[0].state, Some(FetchState::Local));
+    assert_eq!(reports[1].state, Some(FetchState::Downloaded));
+    assert!(reports[2].error.is_some());
+}
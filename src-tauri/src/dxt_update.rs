@@ -0,0 +1,362 @@
+use crate::dxt::{catalog_key, load_manifest, read_catalog_manifest};
+use crate::dxt_bundle::{
+    extension_id, extensions_dir, extract_bundle, inspect_bundle, load_registry,
+    register_extension, registered, save_settings, saved_settings, ExtensionSource,
+    ExtensionTarget, InstalledExtension,
+};
+use crate::dxt_compat::parse_version;
+use crate::dxt_manifest::{format_errors, parse_manifest, UserConfigOption, UserConfigType};
+use crate::dxt_resolve::resolve_dxt;
+use crate::dxt_sources::{get_manifest_origins, manifest_key};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::task;
+
+// Installing catalog extensions through the registry, and upgrading any registered
+// extension once its catalog entry (or a newer bundle) has a higher `version`.
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ExtensionUpdate {
+    pub id: String,
+    pub installed: String,
+    pub available: String,
+    pub catalog_key: String,
+    pub source: Option<ExtensionSource>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RenamedSetting {
+    pub from: String,
+    pub to: String,
+}
+
+/// Saved settings carried over to a new manifest's `user_config`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SettingsMigration {
+    pub settings: Map<String, Value>,
+    pub renamed: Vec<RenamedSetting>,
+    /// Keys with no counterpart in the new manifest
+    pub dropped: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExtensionUpgrade {
+    pub extension: InstalledExtension,
+    pub previous_version: String,
+    pub renamed: Vec<RenamedSetting>,
+    pub dropped: Vec<String>,
+}
+
+fn version(text: &str) -> Option<Version> {
+    Version::parse(text.trim().trim_start_matches('v'))
+        .ok()
+        .or_else(|| parse_version(text))
+}
+
+/// Whether `available` is a higher version than `installed`; unreadable versions never are
+pub(crate) fn is_newer(installed: &str, available: &str) -> bool {
+    match (version(installed), version(available)) {
+        (Some(installed), Some(available)) => available > installed,
+        _ => false,
+    }
+}
+
+fn keys_of_type<'a>(
+    keys: &[&'a String],
+    config: &BTreeMap<String, UserConfigOption>,
+    option_type: &UserConfigType,
+) -> Vec<&'a String> {
+    keys.iter()
+        .copied()
+        .filter(|k| config[*k].option_type == *option_type)
+        .collect()
+}
+
+/// Carry `settings` from the `old` user_config to the `new` one. A key the new manifest
+/// lacks moves to the new key with the same title, or failing that to the only new
+/// key of the same type when exactly one old key of that type went away.
+pub(crate) fn migrate_settings(
+    old: &BTreeMap<String, UserConfigOption>,
+    new: &BTreeMap<String, UserConfigOption>,
+    settings: &Map<String, Value>,
+) -> SettingsMigration {
+    let removed: Vec<&String> = old.keys().filter(|k| !new.contains_key(*k)).collect();
+    let added: Vec<&String> = new
+        .keys()
+        .filter(|k| !old.contains_key(*k) && !settings.contains_key(*k))
+        .collect();
+
+    let mut migration = SettingsMigration {
+        settings: Map::new(),
+        renamed: Vec::new(),
+        dropped: Vec::new(),
+    };
+    let mut taken: Vec<&String> = Vec::new();
+    for (key, value) in settings {
+        if new.contains_key(key) {
+            migration.settings.insert(key.clone(), value.clone());
+            continue;
+        }
+        let target = old.get(key).and_then(|option| {
+            let by_title: Vec<&String> = added
+                .iter()
+                .copied()
+                .filter(|k| !taken.contains(k))
+                .filter(|k| new[*k].title.eq_ignore_ascii_case(&option.title))
+                .collect();
+            if let [only] = by_title[..] {
+                return Some(only);
+            }
+            let gone = keys_of_type(&removed, old, &option.option_type);
+            let candidates = keys_of_type(&added, new, &option.option_type);
+            match (&gone[..], &candidates[..]) {
+                ([_], [only]) if !taken.contains(only) => Some(*only),
+                _ => None,
+            }
+        });
+        match target {
+            Some(to) => {
+                taken.push(to);
+                migration.settings.insert(to.clone(), value.clone());
+                migration.renamed.push(RenamedSetting {
+                    from: key.clone(),
+                    to: to.clone(),
+                });
+            }
+            None => migration.dropped.push(key.clone()),
+        }
+    }
+    migration
+}
+
+fn catalog_manifest_path(key: &str) -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    Ok(home
+        .join(".config/finder/dxt")
+        .join(key)
+        .join("manifest.json"))
+}
+
+fn manifest_str(manifest: &Value, field: &str) -> String {
+    manifest
+        .get(field)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+const DIRNAME: &str = "${__dirname}";
+
+/// Whether the command, an argument or an env value still has `${__dirname}` in it
+fn uses_dirname(server: &Value) -> bool {
+    let has = |value: &Value| value.as_str().is_some_and(|s| s.contains(DIRNAME));
+    server.get("command").is_some_and(has)
+        || server
+            .get("args")
+            .and_then(Value::as_array)
+            .is_some_and(|args| args.iter().any(has))
+        || server
+            .get("env")
+            .and_then(Value::as_object)
+            .is_some_and(|env| env.values().any(has))
+}
+
+/// Resolve `manifest` with `settings`, failing with every settings error. Without
+/// `dirname` there are no extension files, so a server that points into them fails too.
+pub(crate) fn resolve_server(
+    id: &str,
+    manifest: &Value,
+    settings: &Value,
+    dirname: Option<&Path>,
+) -> Result<Value, String> {
+    let resolved = resolve_dxt(manifest, settings, dirname);
+    let server = resolved
+        .server
+        .ok_or_else(|| format!("Cannot install {}: {}", id, format_errors(&resolved.errors)))?;
+    if dirname.is_none() && uses_dirname(&server) {
+        return Err(format!(
+            "Cannot install {}: it runs files from its bundle ({}), install the .dxt instead",
+            id, DIRNAME
+        ));
+    }
+    Ok(server)
+}
+
+/// Install a catalog manifest as server `<id>` in every target, recorded in the
+/// extension registry. Without `settings` the ones saved for the entry are used.
+#[tauri::command]
+pub async fn install_catalog_extension(
+    user: String,
+    repo: String,
+    targets: Vec<ExtensionTarget>,
+    settings: Option<Value>,
+) -> Result<InstalledExtension, String> {
+    let key = catalog_key(&user, &repo)?;
+    let manifest = load_manifest(user, repo).await?;
+    let id = extension_id(&manifest_str(&manifest, "name"))?;
+    let settings = match settings {
+        Some(settings) => settings,
+        None => saved_settings(Some(&key)).await,
+    };
+    let server = resolve_server(&id, &manifest, &settings, None)?;
+    save_settings(Some(&key), &settings).await?;
+
+    let source = get_manifest_origins()?.remove(&key).map(|o| o.source);
+    let extension = InstalledExtension {
+        id,
+        name: manifest_str(&manifest, "name"),
+        version: manifest_str(&manifest, "version"),
+        display_name: manifest
+            .get("display_name")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        dir: None,
+        server,
+        targets: Vec::new(),
+        source: Some(ExtensionSource::Catalog { source }),
+        catalog_key: Some(key),
+        manifest: Some(manifest),
+    };
+    register_extension(&extensions_dir()?, extension, targets).await
+}
+
+/// Installed extensions whose catalog entry has a higher version
+#[tauri::command]
+pub async fn check_extension_updates() -> Result<Vec<ExtensionUpdate>, String> {
+    let registry = load_registry(&extensions_dir()?).await?;
+    let mut updates = Vec::new();
+    for id in registry.keys() {
+        let Some(extension) = registered(&registry, id) else {
+            continue;
+        };
+        let Some(key) = extension.catalog_key.clone() else {
+            continue;
+        };
+        let Ok(manifest) = read_catalog_manifest(&catalog_manifest_path(&key)?).await else {
+            continue;
+        };
+        let available = manifest_str(&manifest, "version");
+        if is_newer(&extension.version, &available) {
+            updates.push(ExtensionUpdate {
+                id: extension.id,
+                installed: extension.version,
+                available,
+                catalog_key: key,
+                source: extension.source,
+            });
+        }
+    }
+    Ok(updates)
+}
+
+/// Upgrade an installed extension to its catalog entry, or to `bundle_path` for one
+/// installed from a `.dxt`. Saved settings are migrated to the new `user_config` and
+/// the new server entry is written to every client it was registered in.
+#[tauri::command]
+pub async fn upgrade_dxt_extension(
+    id: String,
+    bundle_path: Option<String>,
+) -> Result<ExtensionUpgrade, String> {
+    let dir = extensions_dir()?;
+    let registry = load_registry(&dir).await?;
+    let installed =
+        registered(&registry, &id).ok_or_else(|| format!("Extension '{}' is not installed", id))?;
+
+    let (manifest, source) = match (bundle_path, &installed.source, &installed.catalog_key) {
+        (Some(path), _, _) => {
+            let bundle = PathBuf::from(&path);
+            let info = task::spawn_blocking(move || inspect_bundle(&bundle))
+                .await
+                .map_err(|e| format!("Failed to read bundle: {}", e))??;
+            (info.manifest, ExtensionSource::Bundle { path })
+        }
+        (None, Some(ExtensionSource::Bundle { .. }), _) => {
+            return Err(format!(
+                "{} was installed from a .dxt; pass the newer bundle to upgrade it",
+                id
+            ))
+        }
+        (None, _, Some(key)) => {
+            let manifest = read_catalog_manifest(&catalog_manifest_path(key)?).await?;
+            let source = get_manifest_origins()?.remove(key).map(|o| o.source);
+            (manifest, ExtensionSource::Catalog { source })
+        }
+        (None, _, None) => return Err(format!("{} has no catalog entry to upgrade from", id)),
+    };
+
+    let new_id = extension_id(&manifest_str(&manifest, "name"))?;
+    if new_id != id {
+        return Err(format!("The new manifest is for {}, not {}", new_id, id));
+    }
+    let version = manifest_str(&manifest, "version");
+    if !is_newer(&installed.version, &version) {
+        return Err(format!(
+            "{} {} is not newer than the installed {}",
+            id, version, installed.version
+        ));
+    }
+
+    let new_config = parse_manifest(&manifest)
+        .map_err(|errors| format!("Invalid manifest: {}", format_errors(&errors)))?
+        .user_config;
+    let old_config = installed
+        .manifest
+        .as_ref()
+        .and_then(|m| parse_manifest(m).ok())
+        .map(|m| m.user_config)
+        .unwrap_or_default();
+    let saved = saved_settings(installed.catalog_key.as_deref()).await;
+    let migration = migrate_settings(
+        &old_config,
+        &new_config,
+        saved.as_object().unwrap_or(&Map::new()),
+    );
+    let settings = Value::Object(migration.settings.clone());
+
+    let is_bundle = matches!(source, ExtensionSource::Bundle { .. });
+    let install_dir = dir.join(&id);
+    let server = resolve_server(
+        &id,
+        &manifest,
+        &settings,
+        is_bundle.then_some(install_dir.as_path()),
+    )?;
+    if let ExtensionSource::Bundle { path } = &source {
+        let (path, extract_dir) = (path.clone(), dir.clone());
+        task::spawn_blocking(move || extract_bundle(Path::new(&path), &extract_dir))
+            .await
+            .map_err(|e| format!("Failed to extract bundle: {}", e))??;
+    }
+    let catalog_key = manifest_key(&manifest).ok();
+    save_settings(catalog_key.as_deref(), &settings).await?;
+    println!(
+        "[Dxt] upgraded {} from {} to {}",
+        id, installed.version, version
+    );
+
+    let extension = InstalledExtension {
+        id,
+        name: manifest_str(&manifest, "name"),
+        version,
+        display_name: manifest
+            .get("display_name")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        dir: is_bundle.then(|| install_dir.to_string_lossy().to_string()),
+        server,
+        targets: Vec::new(),
+        source: Some(source),
+        catalog_key,
+        manifest: Some(manifest),
+    };
+    let targets = installed.targets.clone();
+    Ok(ExtensionUpgrade {
+        extension: register_extension(&dir, extension, targets).await?,
+        previous_version: installed.version,
+        renamed: migration.renamed,
+        dropped: migration.dropped,
+    })
+}
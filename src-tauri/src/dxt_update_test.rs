@@ -0,0 +1,96 @@
+// Tests for DXT extension upgrades
+use crate::dxt_manifest::parse_manifest;
+use crate::dxt_manifest_test::manifest_with;
+use crate::dxt_update::{is_newer, migrate_settings, resolve_server, RenamedSetting};
+use serde_json::json;
+
+fn user_config(
+    config: serde_json::Value,
+) -> std::collections::BTreeMap<String, crate::dxt_manifest::UserConfigOption> {
+    parse_manifest(&manifest_with(json!({
+        "server": {
+            "type": "binary",
+            "entry_point": "files",
+            "mcp_config": {"command": "files", "args": []}
+        },
+        "user_config": config
+    })))
+    .unwrap()
+    .user_config
+}
+
+fn option(kind: &str, title: &str) -> serde_json::Value {
+    json!({"type": kind, "title": title, "description": title})
+}
+
+#[test]
+fn test_compares_versions() {
+    assert!(is_newer("1.2.0", "1.10.0"));
+    assert!(is_newer("v1.2", "1.2.1"));
+    assert!(is_newer("1.0.0-beta.1", "1.0.0"));
+    assert!(!is_newer("2.0.0", "1.9.9"));
+    assert!(!is_newer("1.0.0", "latest"));
+}
+
+#[test]
+fn test_migrates_renamed_settings() {
+    let old = user_config(json!({
+        "api_key": option("string", "API key"),
+        "root": option("directory", "Root"),
+        "limit": option("number", "Limit"),
+        "verbose": option("boolean", "Verbose"),
+    }));
+    let new = user_config(json!({
+        "token": option("string", "API Key"),
+        "roots": option("directory", "Directories"),
+        "limit": option("number", "Limit"),
+    }));
+    let settings = json!({
+        "api_key": "secret",
+        "root": "/data",
+        "limit": 10,
+        "verbose": true,
+    });
+
+    let migration = migrate_settings(&old, &new, settings.as_object().unwrap());
+    assert_eq!(
+        serde_json::Value::Object(migration.settings),
+        json!({"token": "secret", "roots": "/data", "limit": 10})
+    );
+    let mut renamed = migration.renamed;
+    renamed.sort_by(|a, b| a.from.cmp(&b.from));
+    assert_eq!(
+        renamed,
+        vec![
+            RenamedSetting {
+                from: "api_key".to_string(),
+                to: "token".to_string()
+            },
+            RenamedSetting {
+                from: "root".to_string(),
+                to: "roots".to_string()
+            },
+        ]
+    );
+    assert_eq!(migration.dropped, vec!["verbose".to_string()]);
+}
+
+#[test]
+fn test_refuses_servers_that_need_bundle_files() {
+    let bundled = manifest_with(json!({}));
+    let err = resolve_server("files", &bundled, &json!({}), None).unwrap_err();
+    assert!(err.contains("${__dirname}"), "{}", err);
+
+    let mut env_only = manifest_with(json!({
+        "server": {"mcp_config": {"args": [], "env": {"DATA": "${__dirname}/data"}}}
+    }));
+    assert!(resolve_server("files", &env_only, &json!({}), None).is_err());
+
+    let dir = std::path::Path::new("/ext/files");
+    let server = resolve_server("files", &bundled, &json!({}), Some(dir)).unwrap();
+    assert_eq!(server["args"], json!(["/ext/files/server/index.js"]));
+
+    env_only["server"]["mcp_config"] = json!({"command": "npx", "args": ["-y", "files-mcp"]});
+    let server = resolve_server("files", &env_only, &json!({}), None).unwrap();
+    assert_eq!(server["command"], "npx");
+}
@@ -0,0 +1,253 @@
+use crate::claude_code_commands::{get_claude_config_path, read_claude_config};
+use crate::client::ClientConfig;
+use crate::gemini;
+use crate::json_manager::utils::{get_key_by_client, is_per_server_disabled_client};
+use crate::json_manager::JsonManager;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Keys whose values are compared entry by entry, so a changed token shows up as `env.TOKEN`
+const NESTED_KEYS: [&str; 2] = ["env", "headers"];
+
+/// One config file feeding the merge, lowest precedence first
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScopeSource {
+    pub scope: String,
+    pub path: String,
+    pub exists: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShadowedEntry {
+    pub scope: String,
+    pub origin: String,
+    pub config: Value,
+    /// Keys where the shadowed entry differs from the effective one; values are not
+    /// repeated here since they are often secrets
+    pub differing_keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EffectiveServer {
+    pub name: String,
+    pub config: Value,
+    pub scope: String,
+    pub origin: String,
+    /// Set for clients that keep disabled servers in place (`disabled: true`)
+    pub disabled: bool,
+    pub shadowed: Vec<ShadowedEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EffectiveConfig {
+    pub client: String,
+    pub project: String,
+    pub sources: Vec<ScopeSource>,
+    pub servers: Vec<EffectiveServer>,
+}
+
+/// Servers read from one scope
+pub(crate) struct ScopeServers {
+    pub scope: String,
+    pub origin: String,
+    pub servers: Map<String, Value>,
+}
+
+/// Keys (and `env.X` / `headers.X` sub-keys) whose values differ between two entries
+pub(crate) fn differing_keys(a: &Value, b: &Value) -> Vec<String> {
+    let empty = Map::new();
+    let a = a.as_object().unwrap_or(&empty);
+    let b = b.as_object().unwrap_or(&empty);
+    let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+
+    let mut differing = Vec::new();
+    for key in keys {
+        let (left, right) = (a.get(key), b.get(key));
+        if left == right {
+            continue;
+        }
+        let nested = (
+            left.and_then(|v| v.as_object()),
+            right.and_then(|v| v.as_object()),
+        );
+        match nested {
+            (Some(left), Some(right)) if NESTED_KEYS.contains(&key.as_str()) => {
+                let sub_keys: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+                for sub_key in sub_keys {
+                    if left.get(sub_key) != right.get(sub_key) {
+                        differing.push(format!("{}.{}", key, sub_key));
+                    }
+                }
+            }
+            _ => differing.push(key.clone()),
+        }
+    }
+    differing
+}
+
+/// Merge scopes given lowest precedence first; a later scope replaces a server of the
+/// same name and the replaced entries are kept as `shadowed`.
+pub(crate) fn merge_scopes(
+    scopes: Vec<ScopeServers>,
+    per_server_disabled: bool,
+) -> Vec<EffectiveServer> {
+    let mut merged: Vec<EffectiveServer> = Vec::new();
+    for scope in scopes {
+        for (name, config) in scope.servers {
+            let disabled = per_server_disabled
+                && config
+                    .get("disabled")
+                    .and_then(|d| d.as_bool())
+                    .unwrap_or(false);
+            let entry = EffectiveServer {
+                name: name.clone(),
+                config,
+                scope: scope.scope.clone(),
+                origin: scope.origin.clone(),
+                disabled,
+                shadowed: Vec::new(),
+            };
+            match merged.iter_mut().find(|s| s.name == name) {
+                Some(existing) => {
+                    let previous = std::mem::replace(existing, entry);
+                    let differing = differing_keys(&previous.config, &existing.config);
+                    existing.shadowed = previous.shadowed;
+                    existing.shadowed.push(ShadowedEntry {
+                        scope: previous.scope,
+                        origin: previous.origin,
+                        config: previous.config,
+                        differing_keys: differing,
+                    });
+                }
+                None => merged.push(entry),
+            }
+        }
+    }
+    merged.sort_by(|a, b| a.name.cmp(&b.name));
+    merged
+}
+
+fn servers_at(json: &Value, key: &str) -> Map<String, Value> {
+    json.get(key)
+        .and_then(|s| s.as_object())
+        .cloned()
+        .unwrap_or_default()
+}
+
+async fn read_json_scope(client: &str, path: &Path) -> Result<Map<String, Value>, String> {
+    let mut json = JsonManager::read_json_file(path).await?;
+    if client == "gemini" {
+        gemini::map_server_sections(&mut json, gemini::gemini_to_common);
+    }
+    Ok(servers_at(&json, get_key_by_client(client)))
+}
+
+fn source(scope: &str, path: &Path, error: Option<String>) -> ScopeSource {
+    ScopeSource {
+        scope: scope.to_string(),
+        path: path.to_string_lossy().to_string(),
+        exists: path.exists(),
+        error,
+    }
+}
+
+/// A missing file reads as no servers; a broken one as no servers plus the error
+fn read_optional<F>(path: &Path, read: F) -> (Value, Option<String>)
+where
+    F: FnOnce(&Path) -> Result<Value, String>,
+{
+    if !path.exists() {
+        return (Value::Null, None);
+    }
+    match read(path) {
+        Ok(value) => (value, None),
+        Err(e) => (Value::Null, Some(e)),
+    }
+}
+
+/// Claude Code: user (`~/.claude.json`) < project (`.mcp.json`) < local (per-project
+/// entry in `~/.claude.json`)
+fn claude_code_scopes(project_dir: &str) -> Result<(Vec<ScopeSource>, Vec<ScopeServers>), String> {
+    let claude_path = get_claude_config_path(None)?;
+    let project_file = Path::new(project_dir).join(".mcp.json");
+
+    let (config, config_error) = read_optional(&claude_path, read_claude_config);
+    let (project, project_error) = read_optional(&project_file, |path| {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))
+    });
+
+    let local = config
+        .get("projects")
+        .and_then(|p| p.get(project_dir))
+        .cloned()
+        .unwrap_or(Value::Null);
+    let origin = |path: &PathBuf| path.to_string_lossy().to_string();
+
+    let sources = vec![
+        source("user", &claude_path, config_error.clone()),
+        source("project", &project_file, project_error),
+        source("local", &claude_path, config_error),
+    ];
+    let scopes = vec![
+        ScopeServers {
+            scope: "user".to_string(),
+            origin: origin(&claude_path),
+            servers: servers_at(&config, "mcpServers"),
+        },
+        ScopeServers {
+            scope: "project".to_string(),
+            origin: origin(&project_file),
+            servers: servers_at(&project, "mcpServers"),
+        },
+        ScopeServers {
+            scope: "local".to_string(),
+            origin: origin(&claude_path),
+            servers: servers_at(&local, "mcpServers"),
+        },
+    ];
+    Ok((sources, scopes))
+}
+
+/// Servers that actually run for `client` in `project_dir`, with the file each comes from
+/// and any entries of the same name it overrides
+#[tauri::command]
+pub async fn resolve_effective_config(
+    client: String,
+    project_dir: String,
+) -> Result<EffectiveConfig, String> {
+    let (sources, scopes) = match client.as_str() {
+        "claude_code" => claude_code_scopes(&project_dir)?,
+        "cursor" | "copilot" | "roo_code" | "gemini" => {
+            let mut sources = Vec::new();
+            let mut scopes = Vec::new();
+            for (scope, path) in [("global", None), ("project", Some(project_dir.as_str()))] {
+                let path = ClientConfig::new(&client, path).get_path().to_path_buf();
+                let (servers, error) = match read_json_scope(&client, &path).await {
+                    Ok(servers) => (servers, None),
+                    Err(e) => (Map::new(), Some(e)),
+                };
+                sources.push(source(scope, &path, error));
+                scopes.push(ScopeServers {
+                    scope: scope.to_string(),
+                    origin: path.to_string_lossy().to_string(),
+                    servers,
+                });
+            }
+            (sources, scopes)
+        }
+        _ => return Err(format!("Client '{}' has no project scope to merge", client)),
+    };
+
+    let servers = merge_scopes(scopes, is_per_server_disabled_client(&client));
+    Ok(EffectiveConfig {
+        client,
+        project: project_dir,
+        sources,
+        servers,
+    })
+}
@@ -0,0 +1,39 @@
+// Tests for merging global and project scopes
+use crate::effective_config::{merge_scopes, ScopeServers};
+use serde_json::json;
+
+#[test]
+fn test_project_shadows_global_and_reports_differences() {
+    let scope = |scope: &str, servers: serde_json::Value| ScopeServers {
+        scope: scope.to_string(),
+        origin: format!("/{}/mcp.json", scope),
+        servers: servers.as_object().unwrap().clone(),
+    };
+    let merged = merge_scopes(
+        vec![
+            scope(
+                "global",
+                json!({
+                    "github": {"command": "npx", "env": {"GITHUB_TOKEN": "global", "LOG": "1"}},
+                    "fetch": {"command": "uvx"}
+                }),
+            ),
+            scope(
+                "project",
+                json!({"github": {"command": "npx", "env": {"GITHUB_TOKEN": "project", "LOG": "1"}}}),
+            ),
+        ],
+        false,
+    );
+
+    assert_eq!(merged.len(), 2);
+    assert_eq!(merged[0].name, "fetch");
+    assert!(merged[0].shadowed.is_empty());
+
+    let github = &merged[1];
+    assert_eq!(github.scope, "project");
+    assert_eq!(github.config["env"]["GITHUB_TOKEN"], "project");
+    assert_eq!(github.shadowed.len(), 1);
+    assert_eq!(github.shadowed[0].scope, "global");
+    assert_eq!(github.shadowed[0].differing_keys, vec!["env.GITHUB_TOKEN"]);
+}
@@ -0,0 +1,51 @@
+use serde_json::{json, Value};
+
+// Gemini CLI uses `mcpServers` like most clients but spells transports by key:
+// `httpUrl` is streamable HTTP, `url` is SSE and `command` is stdio. It has no `type`.
+// Extra fields (trust, timeout, includeTools, excludeTools, cwd) pass through untouched.
+
+/// Convert a Gemini server entry into the common `type` + `url` shape
+pub fn gemini_to_common(mut cfg: Value) -> Value {
+    let Some(obj) = cfg.as_object_mut() else {
+        return cfg;
+    };
+    if let Some(http_url) = obj.remove("httpUrl") {
+        obj.insert("url".into(), http_url);
+        obj.insert("type".into(), json!("http"));
+    } else if obj.contains_key("url") && !obj.contains_key("type") {
+        obj.insert("type".into(), json!("sse"));
+    } else if obj.contains_key("command") && !obj.contains_key("type") {
+        obj.insert("type".into(), json!("stdio"));
+    }
+    cfg
+}
+
+/// Convert a common server entry into Gemini's key-per-transport shape
+pub fn common_to_gemini(mut cfg: Value) -> Value {
+    let Some(obj) = cfg.as_object_mut() else {
+        return cfg;
+    };
+    let server_type = obj
+        .remove("type")
+        .and_then(|t| t.as_str().map(|s| s.to_string()));
+    let is_http = matches!(
+        server_type.as_deref(),
+        Some("http") | Some("streamable_http") | Some("streamableHttp")
+    );
+    let url_key = if is_http { "httpUrl" } else { "url" };
+    if let Some(url) = obj.remove("url") {
+        obj.insert(url_key.into(), url);
+    }
+    cfg
+}
+
+/// Apply `map` to every server under `mcpServers` and `__disabled`
+pub fn map_server_sections(json: &mut Value, map: fn(Value) -> Value) {
+    for section in ["mcpServers", "__disabled"] {
+        if let Some(servers) = json.get_mut(section).and_then(|s| s.as_object_mut()) {
+            for server in servers.values_mut() {
+                *server = map(server.take());
+            }
+        }
+    }
+}
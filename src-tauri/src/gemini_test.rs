@@ -0,0 +1,27 @@
+// Tests for Gemini CLI field mapping
+use crate::gemini::{common_to_gemini, gemini_to_common};
+use serde_json::json;
+
+#[test]
+fn test_http_url_round_trip() {
+    let gemini = json!({"httpUrl": "https://mcp.example.com", "trust": true, "timeout": 30000});
+    let common = gemini_to_common(gemini.clone());
+    assert_eq!(
+        common,
+        json!({"type": "http", "url": "https://mcp.example.com", "trust": true, "timeout": 30000})
+    );
+    assert_eq!(common_to_gemini(common), gemini);
+}
+
+#[test]
+fn test_sse_and_stdio_keep_their_keys() {
+    let sse = gemini_to_common(json!({"url": "https://sse.example.com"}));
+    assert_eq!(sse["type"], "sse");
+    assert_eq!(
+        common_to_gemini(sse),
+        json!({"url": "https://sse.example.com"})
+    );
+
+    let stdio = json!({"command": "npx", "args": ["srv"], "cwd": "/tmp", "includeTools": ["a"]});
+    assert_eq!(common_to_gemini(gemini_to_common(stdio.clone())), stdio);
+}
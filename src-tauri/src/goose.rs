@@ -0,0 +1,296 @@
+use crate::client::ClientConfig;
+use crate::store::ServerStore;
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+// Goose keeps MCP servers as `extensions` in ~/.config/goose/config.yaml:
+// extensions:
+//   github: { enabled: true, name: github, type: stdio, cmd: npx, args: [...], envs: {...}, timeout: 300 }
+//   remote: { enabled: true, name: remote, type: sse, uri: https://..., envs: {} }
+//   api:    { enabled: true, name: api, type: streamable_http, uri: https://..., headers: {} }
+// Builtin and frontend extensions have no portable form and are left alone.
+
+const EXTENSIONS_KEY: &str = "extensions";
+const DEFAULT_TIMEOUT: u64 = 300;
+
+static GOOSE_CFG_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Convert a Goose extension into the common `mcpServers` shape
+pub(crate) fn goose_to_common(ext: &Value) -> Option<Value> {
+    let obj = ext.as_object()?;
+    let mut out = Map::new();
+    match obj.get("type").and_then(|t| t.as_str())? {
+        "stdio" => {
+            out.insert("type".into(), json!("stdio"));
+            out.insert("command".into(), obj.get("cmd").cloned()?);
+            out.insert(
+                "args".into(),
+                obj.get("args").cloned().unwrap_or_else(|| json!([])),
+            );
+            if let Some(envs) = obj.get("envs").filter(|e| e.is_object()) {
+                out.insert("env".into(), envs.clone());
+            }
+        }
+        "sse" => {
+            out.insert("type".into(), json!("sse"));
+            out.insert("url".into(), obj.get("uri").cloned()?);
+        }
+        "streamable_http" => {
+            out.insert("type".into(), json!("http"));
+            out.insert("url".into(), obj.get("uri").cloned()?);
+            if let Some(headers) = obj.get("headers").filter(|h| h.is_object()) {
+                out.insert("headers".into(), headers.clone());
+            }
+        }
+        _ => return None,
+    }
+    if let Some(timeout) = obj.get("timeout") {
+        out.insert("timeout".into(), timeout.clone());
+    }
+    Some(Value::Object(out))
+}
+
+/// Convert a common server config into a Goose extension, keeping Goose-only keys
+/// (description, bundled, env_keys...) from the extension being replaced.
+pub(crate) fn common_to_goose(
+    name: &str,
+    cfg: &Value,
+    previous: Option<&Value>,
+) -> Result<Value, String> {
+    let mut out = previous
+        .and_then(|p| p.as_object())
+        .cloned()
+        .unwrap_or_default();
+    for key in ["cmd", "args", "envs", "uri", "headers"] {
+        out.remove(key);
+    }
+
+    let server_type = cfg.get("type").and_then(|t| t.as_str());
+    if let Some(command) = cfg.get("command").and_then(|c| c.as_str()) {
+        out.insert("type".into(), json!("stdio"));
+        out.insert("cmd".into(), json!(command));
+        out.insert(
+            "args".into(),
+            cfg.get("args").cloned().unwrap_or_else(|| json!([])),
+        );
+        out.insert(
+            "envs".into(),
+            cfg.get("env").cloned().unwrap_or_else(|| json!({})),
+        );
+    } else if let Some(url) = cfg.get("url").and_then(|u| u.as_str()) {
+        if server_type == Some("sse") {
+            out.insert("type".into(), json!("sse"));
+            out.insert("envs".into(), json!({}));
+        } else {
+            out.insert("type".into(), json!("streamable_http"));
+            out.insert(
+                "headers".into(),
+                cfg.get("headers").cloned().unwrap_or_else(|| json!({})),
+            );
+        }
+        out.insert("uri".into(), json!(url));
+    } else {
+        return Err("Goose extensions need a `command` or `url`".to_string());
+    }
+
+    let disabled = cfg.get("disabled").and_then(|d| d.as_bool()) == Some(true)
+        || cfg.get("isActive").and_then(|a| a.as_bool()) == Some(false)
+        || cfg.get("enabled").and_then(|e| e.as_bool()) == Some(false);
+    out.insert("enabled".into(), json!(!disabled));
+    out.insert("name".into(), json!(name));
+    let timeout = cfg
+        .get("timeout")
+        .or_else(|| out.get("timeout"))
+        .cloned()
+        .unwrap_or_else(|| json!(DEFAULT_TIMEOUT));
+    out.insert("timeout".into(), timeout);
+    Ok(Value::Object(out))
+}
+
+fn is_enabled(ext: &Value) -> bool {
+    ext.get("enabled").and_then(|e| e.as_bool()).unwrap_or(true)
+}
+
+pub(crate) async fn load_document(config_path: &Path) -> Result<serde_yaml_ng::Value, String> {
+    if !config_path.exists() {
+        return Ok(serde_yaml_ng::Value::Mapping(Default::default()));
+    }
+    let content = fs::read_to_string(config_path)
+        .await
+        .map_err(|e| format!("Failed to read Goose config: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_yaml_ng::Value::Mapping(Default::default()));
+    }
+    serde_yaml_ng::from_str(&content).map_err(|e| format!("Failed to parse Goose config: {}", e))
+}
+
+pub(crate) async fn persist_document(
+    config_path: &Path,
+    doc: &serde_yaml_ng::Value,
+) -> Result<(), String> {
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content =
+        serde_yaml_ng::to_string(doc).map_err(|e| format!("Failed to serialize YAML: {}", e))?;
+    let tmp_path = config_path.with_extension("yaml.tmp");
+    let mut file = fs::File::create(&tmp_path)
+        .await
+        .map_err(|e| format!("Failed to create temp file: {}", e))?;
+    file.write_all(content.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write temp file: {}", e))?;
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to flush temp file: {}", e))?;
+    drop(file);
+    crate::config_watcher::note_self_write(config_path, content.as_bytes());
+    fs::rename(&tmp_path, config_path)
+        .await
+        .map_err(|e| format!("Failed to rename temp file: {}", e))
+}
+
+fn extensions_as_json(doc: &serde_yaml_ng::Value) -> Result<Map<String, Value>, String> {
+    match doc.get(EXTENSIONS_KEY) {
+        None | Some(serde_yaml_ng::Value::Null) => Ok(Map::new()),
+        Some(extensions) => serde_json::to_value(extensions)
+            .map_err(|e| format!("Unsupported Goose extensions: {}", e))?
+            .as_object()
+            .cloned()
+            .ok_or_else(|| "Goose `extensions` is not a mapping".to_string()),
+    }
+}
+
+/// Load, mutate and write back `extensions`; every other key of the document is kept
+/// in place (YAML comments are not preserved by the round-trip).
+async fn edit_extensions<F>(config_path: &Path, edit: F) -> Result<(), String>
+where
+    F: FnOnce(&mut Map<String, Value>) -> Result<(), String>,
+{
+    let _guard = GOOSE_CFG_LOCK.lock().await;
+    let mut doc = load_document(config_path).await?;
+    let mut extensions = extensions_as_json(&doc)?;
+    edit(&mut extensions)?;
+
+    let yaml_extensions = serde_yaml_ng::to_value(&extensions)
+        .map_err(|e| format!("Failed to convert extensions to YAML: {}", e))?;
+    let mapping = doc
+        .as_mapping_mut()
+        .ok_or_else(|| "Goose config root is not a mapping".to_string())?;
+    mapping.insert(serde_yaml_ng::Value::from(EXTENSIONS_KEY), yaml_extensions);
+    persist_document(config_path, &doc).await
+}
+
+async fn read_extensions(config_path: &Path) -> Result<Map<String, Value>, String> {
+    extensions_as_json(&load_document(config_path).await?)
+}
+
+fn to_common_map(extensions: &Map<String, Value>, enabled: bool) -> Map<String, Value> {
+    extensions
+        .iter()
+        .filter(|(_, ext)| is_enabled(ext) == enabled)
+        .filter_map(|(name, ext)| goose_to_common(ext).map(|cfg| (name.clone(), cfg)))
+        .collect()
+}
+
+pub struct GooseStore {
+    path: PathBuf,
+}
+
+impl GooseStore {
+    pub fn new(path: Option<&str>) -> Self {
+        Self {
+            path: ClientConfig::new("goose", path).get_path().to_path_buf(),
+        }
+    }
+}
+
+#[async_trait]
+impl ServerStore for GooseStore {
+    fn client(&self) -> &str {
+        "goose"
+    }
+
+    async fn read_servers(&self) -> Result<Map<String, Value>, String> {
+        Ok(to_common_map(&read_extensions(&self.path).await?, true))
+    }
+
+    async fn list_disabled(&self) -> Result<Map<String, Value>, String> {
+        Ok(to_common_map(&read_extensions(&self.path).await?, false))
+    }
+
+    async fn upsert(&self, name: &str, cfg: Value) -> Result<(), String> {
+        edit_extensions(&self.path, |extensions| {
+            let ext = common_to_goose(name, &cfg, extensions.get(name))?;
+            extensions.insert(name.to_string(), ext);
+            Ok(())
+        })
+        .await
+    }
+
+    async fn remove(&self, name: &str) -> Result<(), String> {
+        edit_extensions(&self.path, |extensions| {
+            extensions
+                .remove(name)
+                .map(|_| ())
+                .ok_or_else(|| format!("Goose extension '{}' not found", name))
+        })
+        .await
+    }
+
+    async fn set_enabled(&self, name: &str, enabled: bool) -> Result<(), String> {
+        edit_extensions(&self.path, |extensions| {
+            let ext = extensions
+                .get_mut(name)
+                .and_then(|e| e.as_object_mut())
+                .ok_or_else(|| format!("Goose extension '{}' not found", name))?;
+            ext.insert("enabled".into(), json!(enabled));
+            Ok(())
+        })
+        .await
+    }
+
+    async fn update_disabled(&self, name: &str, cfg: Value) -> Result<(), String> {
+        edit_extensions(&self.path, |extensions| {
+            let mut ext = common_to_goose(name, &cfg, extensions.get(name))?;
+            ext["enabled"] = json!(false);
+            extensions.insert(name.to_string(), ext);
+            Ok(())
+        })
+        .await
+    }
+
+    async fn replace_all(
+        &self,
+        active: &Map<String, Value>,
+        disabled: &Map<String, Value>,
+    ) -> Result<(), String> {
+        edit_extensions(&self.path, |extensions| {
+            let previous = std::mem::take(extensions);
+            // Builtin/frontend extensions stay, they cannot come from another client
+            for (name, ext) in &previous {
+                if goose_to_common(ext).is_none() {
+                    extensions.insert(name.clone(), ext.clone());
+                }
+            }
+            for (name, cfg) in active {
+                let ext = common_to_goose(name, cfg, previous.get(name))?;
+                extensions.insert(name.clone(), ext);
+            }
+            for (name, cfg) in disabled {
+                let mut ext = common_to_goose(name, cfg, previous.get(name))?;
+                ext["enabled"] = json!(false);
+                extensions.insert(name.clone(), ext);
+            }
+            Ok(())
+        })
+        .await
+    }
+}
@@ -0,0 +1,64 @@
+// Tests for the Goose extensions backend
+use crate::goose::{common_to_goose, goose_to_common, GooseStore};
+use crate::store::ServerStore;
+use serde_json::json;
+
+#[test]
+fn test_stdio_and_http_mapping() {
+    let ext = json!({
+        "enabled": true, "name": "github", "type": "stdio", "cmd": "npx",
+        "args": ["-y", "server-github"], "envs": {"TOKEN": "x"}, "timeout": 300,
+        "description": "GitHub"
+    });
+    let common = goose_to_common(&ext).unwrap();
+    assert_eq!(
+        common,
+        json!({"type": "stdio", "command": "npx", "args": ["-y", "server-github"], "env": {"TOKEN": "x"}, "timeout": 300})
+    );
+    assert_eq!(common_to_goose("github", &common, Some(&ext)).unwrap(), ext);
+
+    let http = common_to_goose(
+        "api",
+        &json!({"type": "http", "url": "https://mcp.example.com"}),
+        None,
+    )
+    .unwrap();
+    assert_eq!(http["type"], "streamable_http");
+    assert_eq!(http["uri"], "https://mcp.example.com");
+    assert!(goose_to_common(&json!({"type": "builtin", "name": "developer"})).is_none());
+}
+
+#[tokio::test]
+async fn test_store_keeps_rest_of_document() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.yaml");
+    std::fs::write(
+        &path,
+        "GOOSE_PROVIDER: openai\nextensions:\n  developer:\n    enabled: true\n    name: developer\n    type: builtin\n",
+    )
+    .unwrap();
+
+    let store = GooseStore::new(Some(path.to_str().unwrap()));
+    store
+        .upsert(
+            "fetch",
+            json!({"command": "uvx", "args": ["mcp-server-fetch"]}),
+        )
+        .await
+        .unwrap();
+    store.set_enabled("fetch", false).await.unwrap();
+
+    assert!(store.read_servers().await.unwrap().is_empty());
+    assert_eq!(
+        store.list_disabled().await.unwrap()["fetch"]["command"],
+        "uvx"
+    );
+
+    let doc: serde_yaml_ng::Value =
+        serde_yaml_ng::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(doc["GOOSE_PROVIDER"].as_str(), Some("openai"));
+    assert_eq!(
+        doc["extensions"]["developer"]["type"].as_str(),
+        Some("builtin")
+    );
+}
@@ -0,0 +1,249 @@
+use crate::claude_code_commands::read_claude_config;
+use crate::discovery::discover_clients;
+use crate::json_manager::utils::is_per_server_disabled_client;
+use crate::mcp_sync::read_from_client;
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// The client whose config is the reference every other client is compared with
+pub(crate) const CANONICAL_CLIENT: &str = "mcplinker";
+
+/// Bookkeeping and state keys that say nothing about how a server runs
+const IGNORED_KEYS: [&str; 5] = ["_creator", "updated_at", "disabled", "isActive", "enabled"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CellState {
+    Present,
+    Disabled,
+    Absent,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryCell {
+    pub state: CellState,
+    /// `None` when the server is absent here or has no canonical entry
+    pub matches_canonical: Option<bool>,
+    pub hash: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryRow {
+    pub name: String,
+    pub canonical_hash: Option<String>,
+    /// One cell per entry of `InventoryMatrix::clients`, in the same order
+    pub cells: Vec<InventoryCell>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryClient {
+    pub client: String,
+    pub variant: Option<String>,
+    pub path: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryMatrix {
+    /// Set when the mcplinker config could not be read; nothing is compared then
+    pub canonical_error: Option<String>,
+    pub clients: Vec<InventoryClient>,
+    pub servers: Vec<InventoryRow>,
+}
+
+/// Active and disabled servers of one client, in the common shape
+#[derive(Default)]
+pub(crate) struct ClientServers {
+    pub active: Map<String, Value>,
+    pub disabled: Map<String, Value>,
+}
+
+impl ClientServers {
+    pub(crate) fn get(&self, name: &str) -> Option<(&Value, CellState)> {
+        self.active
+            .get(name)
+            .map(|cfg| (cfg, CellState::Present))
+            .or_else(|| {
+                self.disabled
+                    .get(name)
+                    .map(|cfg| (cfg, CellState::Disabled))
+            })
+    }
+
+    fn names(&self) -> impl Iterator<Item = &String> {
+        self.active.keys().chain(self.disabled.keys())
+    }
+}
+
+/// The parts of a server config that decide how it runs: bookkeeping keys and empty
+/// `args`/`env`/`headers` are dropped and a missing `type` is inferred.
+pub(crate) fn canonical_form(cfg: &Value) -> Value {
+    let Some(obj) = cfg.as_object() else {
+        return cfg.clone();
+    };
+    let mut out: Map<String, Value> = obj
+        .iter()
+        .filter(|(key, _)| !IGNORED_KEYS.contains(&key.as_str()))
+        .filter(|(_, value)| match value {
+            Value::Array(items) => !items.is_empty(),
+            Value::Object(map) => !map.is_empty(),
+            Value::Null => false,
+            _ => true,
+        })
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    if !out.contains_key("type") {
+        let inferred = if out.contains_key("command") {
+            "stdio"
+        } else {
+            "http"
+        };
+        out.insert("type".into(), json!(inferred));
+    }
+    Value::Object(out)
+}
+
+/// Short, stable hash of the canonical form; keys serialize sorted
+pub(crate) fn config_hash(cfg: &Value) -> String {
+    let serialized = serde_json::to_string(&canonical_form(cfg)).unwrap_or_default();
+    digest(&SHA256, serialized.as_bytes())
+        .as_ref()
+        .iter()
+        .take(6)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn section(doc: &Value, key: &str) -> Map<String, Value> {
+    doc.get(key)
+        .and_then(|s| s.as_object())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Read any client through the same paths sync uses
+pub(crate) async fn read_client_servers(
+    client: &str,
+    path: Option<&str>,
+) -> Result<ClientServers, String> {
+    if client == "claude_code" {
+        // Only the user scope is global; local scopes belong to a project
+        let config = match path {
+            Some(path) if Path::new(path).exists() => read_claude_config(Path::new(path))?,
+            _ => Value::Null,
+        };
+        return Ok(ClientServers {
+            active: section(&config, "mcpServers"),
+            disabled: Map::new(),
+        });
+    }
+
+    let path = if client == "codex" { None } else { path };
+    let doc = read_from_client(client, path).await?;
+    let mut servers = ClientServers {
+        active: section(&doc, "mcpServers"),
+        disabled: section(&doc, "__disabled"),
+    };
+    if is_per_server_disabled_client(client) {
+        let flagged: Vec<String> = servers
+            .active
+            .iter()
+            .filter(|(_, cfg)| cfg.get("disabled").and_then(|d| d.as_bool()) == Some(true))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in flagged {
+            if let Some(cfg) = servers.active.remove(&name) {
+                servers.disabled.insert(name, cfg);
+            }
+        }
+    }
+    Ok(servers)
+}
+
+pub(crate) async fn read_canonical() -> Result<ClientServers, String> {
+    read_client_servers(CANONICAL_CLIENT, None).await
+}
+
+pub(crate) fn build_matrix(
+    clients: Vec<(InventoryClient, ClientServers)>,
+    canonical: &ClientServers,
+) -> InventoryMatrix {
+    let names: BTreeSet<String> = clients
+        .iter()
+        .flat_map(|(_, servers)| servers.names().cloned())
+        .chain(canonical.names().cloned())
+        .collect();
+
+    let servers = names
+        .into_iter()
+        .map(|name| {
+            let canonical_hash = canonical.get(&name).map(|(cfg, _)| config_hash(cfg));
+            let cells = clients
+                .iter()
+                .map(|(_, servers)| match servers.get(&name) {
+                    Some((cfg, state)) => {
+                        let hash = config_hash(cfg);
+                        InventoryCell {
+                            state,
+                            matches_canonical: canonical_hash.as_ref().map(|c| *c == hash),
+                            hash: Some(hash),
+                        }
+                    }
+                    None => InventoryCell {
+                        state: CellState::Absent,
+                        matches_canonical: None,
+                        hash: None,
+                    },
+                })
+                .collect();
+            InventoryRow {
+                name,
+                canonical_hash,
+                cells,
+            }
+        })
+        .collect();
+
+    InventoryMatrix {
+        canonical_error: None,
+        clients: clients.into_iter().map(|(client, _)| client).collect(),
+        servers,
+    }
+}
+
+/// Every discovered client with its servers; unreadable ones carry the error
+pub(crate) async fn read_discovered_clients(
+) -> Result<Vec<(InventoryClient, ClientServers)>, String> {
+    let mut clients = Vec::new();
+    for found in discover_clients().await? {
+        let (servers, error) = match read_client_servers(&found.client, Some(&found.path)).await {
+            Ok(servers) => (servers, None),
+            Err(e) => (ClientServers::default(), Some(e)),
+        };
+        let client = InventoryClient {
+            client: found.client,
+            variant: found.variant,
+            path: found.path,
+            error,
+        };
+        clients.push((client, servers));
+    }
+    Ok(clients)
+}
+
+/// Server name x client matrix over every discovered client, compared with mcplinker
+#[tauri::command]
+pub async fn client_inventory_matrix() -> Result<InventoryMatrix, String> {
+    let (canonical, canonical_error) = match read_canonical().await {
+        Ok(canonical) => (canonical, None),
+        Err(e) => (ClientServers::default(), Some(e)),
+    };
+    let clients = read_discovered_clients().await?;
+
+    let mut matrix = build_matrix(clients, &canonical);
+    matrix.canonical_error = canonical_error;
+    Ok(matrix)
+}
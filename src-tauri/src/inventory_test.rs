@@ -0,0 +1,73 @@
+// Tests for the cross-client inventory matrix
+use crate::inventory::{build_matrix, config_hash, CellState, ClientServers, InventoryClient};
+use serde_json::json;
+
+pub(crate) fn client(name: &str) -> InventoryClient {
+    InventoryClient {
+        client: name.to_string(),
+        variant: None,
+        path: format!("/{}/mcp.json", name),
+        error: None,
+    }
+}
+
+pub(crate) fn servers(active: serde_json::Value, disabled: serde_json::Value) -> ClientServers {
+    ClientServers {
+        active: active.as_object().unwrap().clone(),
+        disabled: disabled.as_object().unwrap().clone(),
+    }
+}
+
+#[test]
+fn test_hash_ignores_bookkeeping_and_empty_fields() {
+    let canonical = json!({"type": "stdio", "command": "npx", "args": ["srv"]});
+    let written = json!({"command": "npx", "args": ["srv"], "env": {}, "_creator": "mcp_linker"});
+    assert_eq!(config_hash(&canonical), config_hash(&written));
+    assert_eq!(config_hash(&canonical).len(), 12);
+}
+
+#[test]
+fn test_matrix_cells() {
+    let canonical = servers(
+        json!({"github": {"command": "npx", "env": {"TOKEN": "a"}}}),
+        json!({}),
+    );
+    let matrix = build_matrix(
+        vec![
+            (
+                client("cursor"),
+                servers(
+                    json!({"github": {"command": "npx", "env": {"TOKEN": "a"}}}),
+                    json!({}),
+                ),
+            ),
+            (
+                client("zed"),
+                servers(
+                    json!({}),
+                    json!({"github": {"command": "npx", "env": {"TOKEN": "b"}}}),
+                ),
+            ),
+            (
+                client("gemini"),
+                servers(json!({"fetch": {"command": "uvx"}}), json!({})),
+            ),
+        ],
+        &canonical,
+    );
+
+    assert_eq!(matrix.clients.len(), 3);
+    let names: Vec<&str> = matrix.servers.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["fetch", "github"]);
+
+    let fetch = &matrix.servers[0];
+    assert!(fetch.canonical_hash.is_none());
+    assert_eq!(fetch.cells[2].state, CellState::Present);
+    assert_eq!(fetch.cells[2].matches_canonical, None);
+
+    let github = &matrix.servers[1];
+    assert_eq!(github.cells[0].matches_canonical, Some(true));
+    assert_eq!(github.cells[1].state, CellState::Disabled);
+    assert_eq!(github.cells[1].matches_canonical, Some(false));
+    assert_eq!(github.cells[2].state, CellState::Absent);
+}
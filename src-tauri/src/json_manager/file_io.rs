@@ -1,9 +1,18 @@
+use once_cell::sync::Lazy;
 use serde_json::{json, Value};
 use std::io::ErrorKind;
 use std::path::Path;
 use tokio::fs;
+use tokio::sync::Mutex;
 use tokio::task;
 
+use super::revision::{
+    check_revision, conflict_error, current_revision, revision_for, MISSING_REVISION,
+};
+
+/// Makes the revision check and the write one step for concurrent commands
+static CHECKED_WRITE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
 /// Read JSON file asynchronously
 pub async fn read_json_file(path: &Path) -> Result<Value, String> {
     let path_buf = path.to_path_buf();
@@ -50,7 +59,51 @@ pub async fn write_json_file(path: &Path, content: &Value) -> Result<(), String>
     let json_string = json_string_result?; // Handle the inner Result from the blocking task
 
     // Write the JSON file asynchronously
+    crate::config_watcher::note_self_write(&path_buf, json_string.as_bytes());
     fs::write(&path_buf, json_string)
         .await
         .map_err(|e| format!("Failed to write file: {}", e))
 }
+
+/// Read a JSON file with its revision. With `expected`, fail if the file changed since
+/// the caller read it.
+pub async fn read_json_file_at(
+    path: &Path,
+    expected: Option<&str>,
+) -> Result<(Value, String), String> {
+    let (json, revision) = match fs::read(path).await {
+        Ok(content) => {
+            let revision = revision_for(path, &content).await;
+            let json = serde_json::from_slice(&content)
+                .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+            (json, revision)
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => (json!({}), MISSING_REVISION.to_string()),
+        Err(e) => return Err(format!("Failed to read file: {}", e)),
+    };
+    match expected {
+        Some(expected) if expected != revision => Err(conflict_error(path, expected, &revision)),
+        _ => Ok((json, revision)),
+    }
+}
+
+/// The revision a read-modify-write must still find on disk: the one it read, but only
+/// when the caller asked for a checked write by passing an expected revision
+pub fn checked<'a>(expected: Option<&str>, read: &'a str) -> Option<&'a str> {
+    expected.map(|_| read)
+}
+
+/// Write only if `path` is still at `revision`, or unconditionally without one;
+/// returns the new revision
+pub async fn write_json_file_at(
+    path: &Path,
+    content: &Value,
+    revision: Option<&str>,
+) -> Result<String, String> {
+    let _guard = CHECKED_WRITE_LOCK.lock().await;
+    if let Some(revision) = revision {
+        check_revision(path, revision).await?;
+    }
+    write_json_file(path, content).await?;
+    current_revision(path).await
+}
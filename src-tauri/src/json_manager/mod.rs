@@ -3,6 +3,7 @@ use std::path::Path;
 
 // Module declarations
 pub mod file_io;
+pub mod revision;
 pub mod server_crud;
 pub mod server_state;
 pub mod utils;
@@ -22,18 +23,39 @@ impl JsonManager {
         file_io::write_json_file(path, content).await
     }
 
+    pub async fn read_json_file_at(
+        path: &Path,
+        expected_revision: Option<&str>,
+    ) -> Result<(Value, String), String> {
+        file_io::read_json_file_at(path, expected_revision).await
+    }
+
+    pub async fn write_json_file_at(
+        path: &Path,
+        content: &Value,
+        revision: Option<&str>,
+    ) -> Result<String, String> {
+        file_io::write_json_file_at(path, content, revision).await
+    }
+
     // Server CRUD operations
     pub async fn add_mcp_server(
         path: &Path,
         client: &str,
         name: &str,
         config: Value,
+        expected_revision: Option<&str>,
     ) -> Result<Value, String> {
-        server_crud::add_mcp_server(path, client, name, config).await
+        server_crud::add_mcp_server(path, client, name, config, expected_revision).await
     }
 
-    pub async fn remove_mcp_server(path: &Path, client: &str, name: &str) -> Result<Value, String> {
-        server_crud::remove_mcp_server(path, client, name).await
+    pub async fn remove_mcp_server(
+        path: &Path,
+        client: &str,
+        name: &str,
+        expected_revision: Option<&str>,
+    ) -> Result<Value, String> {
+        server_crud::remove_mcp_server(path, client, name, expected_revision).await
     }
 
     pub async fn update_mcp_server(
@@ -41,16 +63,18 @@ impl JsonManager {
         client: &str,
         name: &str,
         config: Value,
+        expected_revision: Option<&str>,
     ) -> Result<Value, String> {
-        server_crud::update_mcp_server(path, client, name, config).await
+        server_crud::update_mcp_server(path, client, name, config, expected_revision).await
     }
 
     pub async fn batch_delete_mcp_servers(
         path: &Path,
         client: &str,
         server_names: Vec<String>,
+        expected_revision: Option<&str>,
     ) -> Result<Value, String> {
-        server_crud::batch_delete_mcp_servers(path, client, server_names).await
+        server_crud::batch_delete_mcp_servers(path, client, server_names, expected_revision).await
     }
 
     // Server state management operations
@@ -58,12 +82,18 @@ impl JsonManager {
         path: &Path,
         client: &str,
         name: &str,
+        expected_revision: Option<&str>,
     ) -> Result<Value, String> {
-        server_state::disable_mcp_server(path, client, name).await
+        server_state::disable_mcp_server(path, client, name, expected_revision).await
     }
 
-    pub async fn enable_mcp_server(path: &Path, client: &str, name: &str) -> Result<Value, String> {
-        server_state::enable_mcp_server(path, client, name).await
+    pub async fn enable_mcp_server(
+        path: &Path,
+        client: &str,
+        name: &str,
+        expected_revision: Option<&str>,
+    ) -> Result<Value, String> {
+        server_state::enable_mcp_server(path, client, name, expected_revision).await
     }
 
     pub async fn update_disabled_mcp_server(
@@ -71,8 +101,10 @@ impl JsonManager {
         client: &str,
         name: &str,
         config: Value,
+        expected_revision: Option<&str>,
     ) -> Result<Value, String> {
-        server_state::update_disabled_mcp_server(path, client, name, config).await
+        server_state::update_disabled_mcp_server(path, client, name, config, expected_revision)
+            .await
     }
 
     pub async fn list_disabled_servers(path: &Path, client: &str) -> Result<Value, String> {
@@ -0,0 +1,83 @@
+use ring::digest::{digest, SHA256};
+use serde_json::Value;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use tokio::fs;
+
+/// Key under which reads hand out the revision and whole-file writes hand it back.
+/// It is never written to disk.
+pub const REVISION_KEY: &str = "__revision";
+
+/// Revision of a file that does not exist yet
+pub const MISSING_REVISION: &str = "missing";
+
+/// Every conflict error starts with this, so callers can tell it from other failures
+pub const CONFLICT_PREFIX: &str = "Conflict:";
+
+/// Revision token for already-read file content: mtime in nanoseconds plus a short
+/// content hash. The hash catches edits within the mtime resolution.
+pub async fn revision_for(path: &Path, content: &[u8]) -> String {
+    let mtime = fs::metadata(path)
+        .await
+        .ok()
+        .and_then(|meta| meta.modified().ok())
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|since| since.as_nanos())
+        .unwrap_or_default();
+    let hash: String = digest(&SHA256, content)
+        .as_ref()
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("{}-{}", mtime, hash)
+}
+
+/// Current revision of `path`
+pub async fn current_revision(path: &Path) -> Result<String, String> {
+    match fs::read(path).await {
+        Ok(content) => Ok(revision_for(path, &content).await),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(MISSING_REVISION.to_string()),
+        Err(e) => Err(format!("Failed to read file: {}", e)),
+    }
+}
+
+pub fn conflict_error(path: &Path, expected: &str, found: &str) -> String {
+    format!(
+        "{} {} changed since it was read (expected revision {}, found {}); reload and retry",
+        CONFLICT_PREFIX,
+        path.display(),
+        expected,
+        found
+    )
+}
+
+/// Fail with a conflict error unless `path` is still at `expected`
+pub async fn check_revision(path: &Path, expected: &str) -> Result<(), String> {
+    let found = current_revision(path).await?;
+    if found != expected {
+        println!(
+            "[JsonManager] revision conflict on {}: expected {}, found {}",
+            path.display(),
+            expected,
+            found
+        );
+        return Err(conflict_error(path, expected, &found));
+    }
+    Ok(())
+}
+
+/// Attach `revision` to a response document
+pub fn with_revision(mut json: Value, revision: String) -> Value {
+    if let Some(obj) = json.as_object_mut() {
+        obj.insert(REVISION_KEY.to_string(), Value::String(revision));
+    }
+    json
+}
+
+/// Remove and return the revision a caller sent back with a document
+pub fn take_revision(json: &mut Value) -> Option<String> {
+    json.as_object_mut()
+        .and_then(|obj| obj.remove(REVISION_KEY))
+        .and_then(|rev| rev.as_str().map(str::to_string))
+}
@@ -2,7 +2,8 @@ use chrono::Utc;
 use serde_json::{json, Value};
 use std::path::Path;
 
-use super::file_io::{read_json_file, write_json_file};
+use super::file_io::{checked, read_json_file_at, write_json_file_at};
+use super::revision::with_revision;
 use super::utils::{get_key_by_client, normalize_response_key};
 
 /// Add a new MCP server
@@ -11,8 +12,9 @@ pub async fn add_mcp_server(
     client: &str,
     name: &str,
     config: Value,
+    expected_revision: Option<&str>,
 ) -> Result<Value, String> {
-    let mut json = read_json_file(path).await?;
+    let (mut json, revision) = read_json_file_at(path, expected_revision).await?;
     let key = get_key_by_client(client);
 
     if !json.is_object() {
@@ -33,14 +35,19 @@ pub async fn add_mcp_server(
 
     json[key][name] = config;
 
-    write_json_file(path, &json).await?;
+    let revision = write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
     // Normalize response key to mcpServers for client
-    normalize_response_key(json, client)
+    normalize_response_key(json, client).map(|json| with_revision(json, revision))
 }
 
 /// Remove an MCP server
-pub async fn remove_mcp_server(path: &Path, client: &str, name: &str) -> Result<Value, String> {
-    let mut json = read_json_file(path).await?;
+pub async fn remove_mcp_server(
+    path: &Path,
+    client: &str,
+    name: &str,
+    expected_revision: Option<&str>,
+) -> Result<Value, String> {
+    let (mut json, revision) = read_json_file_at(path, expected_revision).await?;
     let key = get_key_by_client(client);
 
     if json.is_object() && json.as_object().unwrap().contains_key(key) {
@@ -49,10 +56,10 @@ pub async fn remove_mcp_server(path: &Path, client: &str, name: &str) -> Result<
         }
     }
 
-    write_json_file(path, &json).await?;
+    let revision = write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
 
     // Normalize response key to mcpServers for client
-    normalize_response_key(json, client)
+    normalize_response_key(json, client).map(|json| with_revision(json, revision))
 }
 
 /// Update an existing MCP server
@@ -61,8 +68,9 @@ pub async fn update_mcp_server(
     client: &str,
     name: &str,
     config: Value,
+    expected_revision: Option<&str>,
 ) -> Result<Value, String> {
-    let mut json = read_json_file(path).await?;
+    let (mut json, revision) = read_json_file_at(path, expected_revision).await?;
     let key = get_key_by_client(client);
 
     if !json.is_object() {
@@ -100,10 +108,10 @@ pub async fn update_mcp_server(
         json[key][name] = config;
     }
 
-    write_json_file(path, &json).await?;
+    let revision = write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
 
     // Normalize response key to mcpServers for client
-    normalize_response_key(json, client)
+    normalize_response_key(json, client).map(|json| with_revision(json, revision))
 }
 
 /// Batch delete multiple MCP servers
@@ -111,10 +119,11 @@ pub async fn batch_delete_mcp_servers(
     path: &Path,
     client: &str,
     server_names: Vec<String>,
+    expected_revision: Option<&str>,
 ) -> Result<Value, String> {
     use super::utils::is_per_server_disabled_client;
 
-    let mut json = read_json_file(path).await?;
+    let (mut json, revision) = read_json_file_at(path, expected_revision).await?;
     let key = get_key_by_client(client);
 
     if !json.is_object() {
@@ -130,8 +139,9 @@ pub async fn batch_delete_mcp_servers(
                 }
             }
         }
-        write_json_file(path, &json).await?;
-        return normalize_response_key(json, client);
+        let revision =
+            write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
+        return normalize_response_key(json, client).map(|json| with_revision(json, revision));
     }
 
     // Default: move from __disabled to active, then delete from active
@@ -178,8 +188,8 @@ pub async fn batch_delete_mcp_servers(
         }
     }
 
-    write_json_file(path, &json).await?;
+    let revision = write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
 
     // Normalize response key to mcpServers for client
-    normalize_response_key(json, client)
+    normalize_response_key(json, client).map(|json| with_revision(json, revision))
 }
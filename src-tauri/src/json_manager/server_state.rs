@@ -1,7 +1,8 @@
 use serde_json::{json, Value};
 use std::path::Path;
 
-use super::file_io::{read_json_file, write_json_file};
+use super::file_io::{checked, read_json_file, read_json_file_at, write_json_file_at};
+use super::revision::with_revision;
 use super::utils::{
     get_key_by_client, is_per_server_disabled_client,
     normalize_response_key,
@@ -13,8 +14,9 @@ pub async fn update_disabled_mcp_server(
     client: &str,
     name: &str,
     config: Value,
+    expected_revision: Option<&str>,
 ) -> Result<Value, String> {
-    let mut json = read_json_file(path).await?;
+    let (mut json, revision) = read_json_file_at(path, expected_revision).await?;
     let key = get_key_by_client(client);
 
     if is_per_server_disabled_client(client) {
@@ -29,8 +31,9 @@ pub async fn update_disabled_mcp_server(
         let mut config_with_disabled = config;
         config_with_disabled["disabled"] = json!(true);
         json[key][name] = config_with_disabled;
-        write_json_file(path, &json).await?;
-        return normalize_response_key(json, client);
+        let revision =
+            write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
+        return normalize_response_key(json, client).map(|json| with_revision(json, revision));
     }
 
     // Default: update __disabled section
@@ -45,15 +48,20 @@ pub async fn update_disabled_mcp_server(
     // Update the disabled server
     json["__disabled"][name] = config;
 
-    write_json_file(path, &json).await?;
+    let revision = write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
 
     // Normalize response key to mcpServers for client
-    normalize_response_key(json, client)
+    normalize_response_key(json, client).map(|json| with_revision(json, revision))
 }
 
 /// Disable an MCP server
-pub async fn disable_mcp_server(path: &Path, client: &str, name: &str) -> Result<Value, String> {
-    let mut json = read_json_file(path).await?;
+pub async fn disable_mcp_server(
+    path: &Path,
+    client: &str,
+    name: &str,
+    expected_revision: Option<&str>,
+) -> Result<Value, String> {
+    let (mut json, revision) = read_json_file_at(path, expected_revision).await?;
     let key = get_key_by_client(client);
 
     if !json.is_object() {
@@ -70,8 +78,9 @@ pub async fn disable_mcp_server(path: &Path, client: &str, name: &str) -> Result
         }
         // Set disabled: true
         json[key][name]["disabled"] = json!(true);
-        write_json_file(path, &json).await?;
-        return normalize_response_key(json, client);
+        let revision =
+            write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
+        return normalize_response_key(json, client).map(|json| with_revision(json, revision));
     }
 
     // Default: move to __disabled section    // Check if server exists in active servers
@@ -96,15 +105,20 @@ pub async fn disable_mcp_server(path: &Path, client: &str, name: &str) -> Result
     // Add to disabled section
     json["__disabled"][name] = server_config;
 
-    write_json_file(path, &json).await?;
+    let revision = write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
 
     // Normalize response key to mcpServers for client
-    normalize_response_key(json, client)
+    normalize_response_key(json, client).map(|json| with_revision(json, revision))
 }
 
 /// Enable an MCP server
-pub async fn enable_mcp_server(path: &Path, client: &str, name: &str) -> Result<Value, String> {
-    let mut json = read_json_file(path).await?;
+pub async fn enable_mcp_server(
+    path: &Path,
+    client: &str,
+    name: &str,
+    expected_revision: Option<&str>,
+) -> Result<Value, String> {
+    let (mut json, revision) = read_json_file_at(path, expected_revision).await?;
     let key = get_key_by_client(client);
 
     if !json.is_object() {
@@ -124,8 +138,9 @@ pub async fn enable_mcp_server(path: &Path, client: &str, name: &str) -> Result<
         }
         // Remove the disabled key
         json[key][name].as_object_mut().unwrap().remove("disabled");
-        write_json_file(path, &json).await?;
-        return normalize_response_key(json, client);
+        let revision =
+            write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
+        return normalize_response_key(json, client).map(|json| with_revision(json, revision));
     }
 
     // Default: move from __disabled section to active
@@ -163,10 +178,10 @@ pub async fn enable_mcp_server(path: &Path, client: &str, name: &str) -> Result<
     // Add to active servers
     json[key][name] = server_config;
 
-    write_json_file(path, &json).await?;
+    let revision = write_json_file_at(path, &json, checked(expected_revision, &revision)).await?;
 
     // Normalize response key to mcpServers for client
-    normalize_response_key(json, client)
+    normalize_response_key(json, client).map(|json| with_revision(json, revision))
 }
 
 /// List all disabled servers
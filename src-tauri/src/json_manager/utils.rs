@@ -16,9 +16,31 @@ pub fn normalize_response_key(mut json: Value, client: &str) -> Result<Value, St
         }
     }
 
+    if client == "gemini" {
+        crate::gemini::map_server_sections(&mut json, crate::gemini::gemini_to_common);
+    }
+
     Ok(json)
 }
 
+/// Move VS Code's `servers` to `mcpServers`. `inputs` and `${input:...}` references
+/// are left as they are.
+pub fn copilot_to_common(json: &mut Value) {
+    if let Some(servers) = json.as_object_mut().and_then(|obj| obj.remove("servers")) {
+        json["mcpServers"] = servers;
+    }
+}
+
+/// Inverse of `copilot_to_common`, so VS Code only ever sees `servers`
+pub fn common_to_copilot(json: &mut Value) {
+    if let Some(servers) = json
+        .as_object_mut()
+        .and_then(|obj| obj.remove("mcpServers"))
+    {
+        json["servers"] = servers;
+    }
+}
+
 /// Get the appropriate key name based on client type
 pub fn get_key_by_client(client: &str) -> &str {
     if client == "copilot" {
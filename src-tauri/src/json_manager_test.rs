@@ -23,7 +23,7 @@ async fn test_update_mcp_server_active() {
         "command": "updated-command",
         "args": ["arg1", "arg2"]
     });
-    let result = JsonManager::update_mcp_server(&config_path, "mcplinker", "test-server", updated_config.clone()).await;
+    let result = JsonManager::update_mcp_server(&config_path, "mcplinker", "test-server", updated_config.clone(), None).await;
     assert!(result.is_ok());
     // Verify the update
     let final_config = JsonManager::read_json_file(&config_path).await.unwrap();
@@ -49,7 +49,7 @@ async fn test_update_mcp_server_disabled() {
         "command": "updated-command",
         "args": ["arg1", "arg2"]
     });
-    let result = JsonManager::update_mcp_server(&config_path, "mcplinker", "test-server", updated_config.clone()).await;
+    let result = JsonManager::update_mcp_server(&config_path, "mcplinker", "test-server", updated_config.clone(), None).await;
     assert!(result.is_ok());
     // Verify the update
     let final_config = JsonManager::read_json_file(&config_path).await.unwrap();
@@ -75,7 +75,7 @@ async fn test_update_disabled_mcp_server() {
         "command": "updated-command",
         "args": ["arg1", "arg2"]
     });
-    let result = JsonManager::update_disabled_mcp_server(&config_path, "mcplinker", "test-server", updated_config.clone()).await;
+    let result = JsonManager::update_disabled_mcp_server(&config_path, "mcplinker", "test-server", updated_config.clone(), None).await;
     assert!(result.is_ok());
     // Verify the update
     let final_config = JsonManager::read_json_file(&config_path).await.unwrap();
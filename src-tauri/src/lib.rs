@@ -9,19 +9,85 @@ use std::sync::{Arc, Mutex};
 mod adapter;
 mod claude_code_commands;
 mod claude_disabled;
+mod claude_projects;
 mod client;
+mod client_defs;
 mod cmd;
 mod codex;
 mod config;
+mod config_watcher;
+mod continue_dev;
+mod discovery;
+mod drift;
 mod dxt;
+mod dxt_bundle;
+mod dxt_compat;
+mod dxt_manifest;
+mod dxt_resolve;
+mod dxt_search;
+mod dxt_sources;
+mod dxt_update;
+mod effective_config;
 mod encryption;
+mod gemini;
 mod git;
+mod goose;
 mod installer;
+mod inventory;
 mod json_manager;
 mod mcp_commands;
 mod mcp_crud;
 mod mcp_sync;
+mod safe_zip;
+mod store;
 mod window;
+mod workspace_scan;
+mod zed;
+
+#[cfg(test)]
+mod claude_code_test;
+#[cfg(test)]
+mod client_defs_test;
+#[cfg(test)]
+mod client_test;
+#[cfg(test)]
+mod config_watcher_test;
+#[cfg(test)]
+mod continue_test;
+#[cfg(test)]
+mod discovery_test;
+#[cfg(test)]
+mod drift_test;
+#[cfg(test)]
+mod dxt_bundle_test;
+#[cfg(test)]
+mod dxt_compat_test;
+#[cfg(test)]
+mod dxt_manifest_test;
+#[cfg(test)]
+mod dxt_resolve_test;
+#[cfg(test)]
+mod dxt_search_test;
+#[cfg(test)]
+mod dxt_sources_test;
+#[cfg(test)]
+mod dxt_update_test;
+#[cfg(test)]
+mod effective_config_test;
+#[cfg(test)]
+mod gemini_test;
+#[cfg(test)]
+mod goose_test;
+#[cfg(test)]
+mod inventory_test;
+#[cfg(test)]
+mod revision_test;
+#[cfg(test)]
+mod safe_zip_test;
+#[cfg(test)]
+mod workspace_scan_test;
+#[cfg(test)]
+mod zed_test;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -58,6 +124,15 @@ pub fn run() {
             mcp_commands::list_disabled_servers,
             mcp_commands::update_disabled_mcp_server,
             mcp_sync::sync_mcp_config,
+            discovery::discover_clients,
+            client_defs::list_client_definitions,
+            workspace_scan::scan_workspace,
+            effective_config::resolve_effective_config,
+            inventory::client_inventory_matrix,
+            drift::detect_config_drift,
+            drift::adopt_into_canonical,
+            drift::reapply_canonical,
+            config_watcher::refresh_config_watcher,
             installer::check_command_exists,
             installer::install_command,
             git::git_clone,
@@ -70,7 +145,27 @@ pub fn run() {
             dxt::read_dxt_setting,
             dxt::save_dxt_setting,
             dxt::download_and_extract_manifests,
+            dxt::refresh_dxt_catalog,
             dxt::check_manifests_exist,
+            dxt::check_catalog_manifests,
+            dxt_manifest::validate_dxt_manifest,
+            dxt_resolve::resolve_dxt_config,
+            dxt_resolve::resolve_saved_dxt_config,
+            dxt_compat::detect_dxt_runtimes,
+            dxt_compat::check_dxt_compatibility,
+            dxt_compat::check_catalog_compatibility,
+            dxt_sources::get_dxt_sources,
+            dxt_sources::save_dxt_sources,
+            dxt_sources::get_manifest_origins,
+            dxt_update::install_catalog_extension,
+            dxt_update::check_extension_updates,
+            dxt_update::upgrade_dxt_extension,
+            dxt_search::search_dxt_catalog,
+            dxt_search::rebuild_dxt_index,
+            dxt_bundle::inspect_dxt_bundle,
+            dxt_bundle::install_dxt_bundle,
+            dxt_bundle::list_installed_extensions,
+            dxt_bundle::uninstall_dxt_extension,
             claude_code_commands::claude_mcp_list,
             claude_code_commands::claude_mcp_get,
             claude_code_commands::claude_mcp_add,
@@ -82,9 +177,18 @@ pub fn run() {
             claude_disabled::claude_disable_server,
             claude_disabled::claude_enable_server,
             claude_disabled::claude_update_disabled,
+            claude_projects::claude_project_inventory,
+            claude_projects::claude_transfer_servers,
         ])
         .manage(Arc::new(Mutex::new(None::<String>)))
         .setup(|_app| {
+            client_defs::init_registry();
+            let handle = _app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = config_watcher::start_config_watcher(handle).await {
+                    println!("[ConfigWatcher] not started: {}", e);
+                }
+            });
             #[cfg(any(windows, target_os = "linux"))]
             {
                 use tauri_plugin_deep_link::DeepLinkExt;
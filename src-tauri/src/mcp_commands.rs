@@ -6,8 +6,10 @@ pub async fn disable_mcp_server(
     client_name: String,
     path: Option<String>,
     server_name: String,
+    expected_revision: Option<String>,
 ) -> Result<Value, String> {
-    let adapter = ClientAdapter::new(&client_name, path.as_deref());
+    let adapter = ClientAdapter::new(&client_name, path.as_deref())
+        .with_revision(expected_revision.as_deref());
     adapter.disable(server_name).await
 }
 
@@ -16,8 +18,10 @@ pub async fn enable_mcp_server(
     client_name: String,
     path: Option<String>,
     server_name: String,
+    expected_revision: Option<String>,
 ) -> Result<Value, String> {
-    let adapter = ClientAdapter::new(&client_name, path.as_deref());
+    let adapter = ClientAdapter::new(&client_name, path.as_deref())
+        .with_revision(expected_revision.as_deref());
     adapter.enable(server_name).await
 }
 
@@ -36,7 +40,9 @@ pub async fn update_disabled_mcp_server(
     path: Option<String>,
     server_name: String,
     server_config: Value,
+    expected_revision: Option<String>,
 ) -> Result<Value, String> {
-    let adapter = ClientAdapter::new(&client_name, path.as_deref());
+    let adapter = ClientAdapter::new(&client_name, path.as_deref())
+        .with_revision(expected_revision.as_deref());
     adapter.update_disabled(server_name, server_config).await
 }
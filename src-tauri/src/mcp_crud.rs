@@ -82,8 +82,10 @@ pub async fn add_mcp_server(
     path: Option<String>,
     server_name: String,
     server_config: Value,
+    expected_revision: Option<String>,
 ) -> Result<Value, String> {
-    let adapter = ClientAdapter::new(&client_name, path.as_deref());
+    let adapter = ClientAdapter::new(&client_name, path.as_deref())
+        .with_revision(expected_revision.as_deref());
     adapter.add(server_name, server_config).await
 }
 
@@ -92,8 +94,10 @@ pub async fn remove_mcp_server(
     client_name: String,
     path: Option<String>,
     server_name: String,
+    expected_revision: Option<String>,
 ) -> Result<Value, String> {
-    let adapter = ClientAdapter::new(&client_name, path.as_deref());
+    let adapter = ClientAdapter::new(&client_name, path.as_deref())
+        .with_revision(expected_revision.as_deref());
     adapter.remove(server_name).await
 }
 
@@ -103,8 +107,10 @@ pub async fn update_mcp_server(
     path: Option<String>,
     server_name: String,
     server_config: Value,
+    expected_revision: Option<String>,
 ) -> Result<Value, String> {
-    let adapter = ClientAdapter::new(&client_name, path.as_deref());
+    let adapter = ClientAdapter::new(&client_name, path.as_deref())
+        .with_revision(expected_revision.as_deref());
     adapter.update(server_name, server_config).await
 }
 
@@ -113,7 +119,9 @@ pub async fn batch_delete_mcp_servers(
     client_name: String,
     path: Option<String>,
     server_names: Vec<String>,
+    expected_revision: Option<String>,
 ) -> Result<Value, String> {
-    let adapter = ClientAdapter::new(&client_name, path.as_deref());
+    let adapter = ClientAdapter::new(&client_name, path.as_deref())
+        .with_revision(expected_revision.as_deref());
     adapter.batch_delete(server_names).await
 }
@@ -1,8 +1,12 @@
 use crate::claude_code_commands;
 use crate::client::ClientConfig;
 use crate::codex as codex_cmds;
-use crate::json_manager::utils::is_per_server_disabled_client;
+use crate::gemini;
+use crate::json_manager::utils::{
+    common_to_copilot, copilot_to_common, is_per_server_disabled_client,
+};
 use crate::json_manager::JsonManager;
+use crate::store::store_for;
 use serde_json::json;
 use serde_json::Value as JsonValue;
 
@@ -144,7 +148,10 @@ pub async fn sync_mcp_config(
     write_to_client(&to_client, to_path.as_deref(), to_json, override_all).await
 }
 
-async fn read_from_client(client: &str, path: Option<&str>) -> Result<JsonValue, String> {
+pub(crate) async fn read_from_client(
+    client: &str,
+    path: Option<&str>,
+) -> Result<JsonValue, String> {
     if client == "codex" {
         let servers = codex_cmds::read_mcp_servers().await?;
         let disabled = codex_cmds::list_disabled().await?;
@@ -155,27 +162,23 @@ async fn read_from_client(client: &str, path: Option<&str>) -> Result<JsonValue,
         let list = claude_code_commands::claude_mcp_list(workdir.to_string()).await?;
         let mut mapped = serde_json::Map::new();
         for s in list {
-            let mut v = serde_json::Map::new();
-            v.insert("type".into(), json!(s.r#type));
-            if let Some(u) = s.url {
-                v.insert("url".into(), json!(u));
-            }
-            if let Some(c) = s.command {
-                v.insert("command".into(), json!(c));
-            }
-            if let Some(a) = s.args {
-                v.insert("args".into(), json!(a));
-            }
-            if let Some(e) = s.env {
-                v.insert("env".into(), json!(e));
-            }
-            mapped.insert(s.name, json!(v));
+            let v = claude_code_commands::server_to_json(&s)?;
+            mapped.insert(s.name, v);
         }
         Ok(json!({"mcpServers": mapped}))
+    } else if let Some(store) = store_for(client, path) {
+        store.read_document().await
     } else {
         let cfg = ClientConfig::new(client, path);
         let p = cfg.get_path();
-        JsonManager::read_json_file(p).await
+        let mut json = JsonManager::read_json_file(p).await?;
+        if client == "gemini" {
+            gemini::map_server_sections(&mut json, gemini::gemini_to_common);
+        }
+        if client == "copilot" {
+            copilot_to_common(&mut json);
+        }
+        Ok(json)
     }
 }
 
@@ -255,35 +258,52 @@ async fn write_to_client(
                 })
                 .unwrap_or_else(|| "http".to_string());
 
-            // map cfg_val to ClaudeCodeServer
-            let server = claude_code_commands::ClaudeCodeServer {
-                name: name.clone(),
-                r#type: inferred_type,
-                url: cfg_val
-                    .get("url")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string()),
-                command: cfg_val
-                    .get("command")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string()),
-                args: cfg_val.get("args").and_then(|v| v.as_array()).map(|arr| {
-                    arr.iter()
-                        .filter_map(|x| x.as_str().map(|s| s.to_string()))
-                        .collect()
-                }),
-                env: cfg_val.get("env").and_then(|v| v.as_object()).map(|m| {
-                    m.iter()
-                        .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-                        .collect()
-                }),
-            };
+            // map cfg_val to ClaudeCodeServer, keeping headers and unknown keys
+            let mut server = claude_code_commands::parse_server_config(&name, &cfg_val)?;
+            server.r#type = inferred_type;
             let _ = claude_code_commands::claude_mcp_add(server, workdir.to_string()).await;
         }
         Ok(())
+    } else if let Some(store) = store_for(client, path) {
+        let active = content
+            .get("mcpServers")
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+        let disabled = content
+            .get("__disabled")
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+
+        if override_all {
+            store.replace_all(&active, &disabled).await
+        } else {
+            // Merge only missing names, leaving existing entries untouched
+            let mut current = store.read_servers().await?;
+            current.extend(store.list_disabled().await?);
+            for (name, cfg_val) in active {
+                if !current.contains_key(&name) {
+                    store.upsert(&name, cfg_val).await?;
+                }
+            }
+            for (name, cfg_val) in disabled {
+                if !current.contains_key(&name) {
+                    store.update_disabled(&name, cfg_val).await?;
+                }
+            }
+            Ok(())
+        }
     } else {
         let cfg = ClientConfig::new(client, path);
         let p = cfg.get_path();
+        let mut content = content;
+        if client == "gemini" {
+            gemini::map_server_sections(&mut content, gemini::common_to_gemini);
+        }
+        if client == "copilot" {
+            common_to_copilot(&mut content);
+        }
         JsonManager::write_json_file(p, &content).await
     }
 }
@@ -0,0 +1,86 @@
+// Tests for revision tokens and conflict detection on JSON configs
+use crate::json_manager::revision::{
+    current_revision, CONFLICT_PREFIX, MISSING_REVISION, REVISION_KEY,
+};
+use crate::json_manager::JsonManager;
+use serde_json::json;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_stale_revision_is_refused() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("mcp.json");
+    assert_eq!(
+        current_revision(&config_path).await.unwrap(),
+        MISSING_REVISION
+    );
+
+    JsonManager::write_json_file(&config_path, &json!({"mcpServers": {}}))
+        .await
+        .unwrap();
+    let (_, revision) = JsonManager::read_json_file_at(&config_path, None)
+        .await
+        .unwrap();
+
+    // An editor changes the file after mcp-linker read it
+    std::fs::write(
+        &config_path,
+        r#"{"mcpServers": {"manual": {"command": "run"}}}"#,
+    )
+    .unwrap();
+
+    let result = JsonManager::add_mcp_server(
+        &config_path,
+        "cursor",
+        "fetch",
+        json!({"command": "uvx"}),
+        Some(&revision),
+    )
+    .await;
+    assert!(result.unwrap_err().starts_with(CONFLICT_PREFIX));
+    let on_disk = JsonManager::read_json_file(&config_path).await.unwrap();
+    assert!(on_disk["mcpServers"].get("fetch").is_none());
+
+    // With the current revision the write goes through and hands out the next one
+    let revision = current_revision(&config_path).await.unwrap();
+    let result = JsonManager::add_mcp_server(
+        &config_path,
+        "cursor",
+        "fetch",
+        json!({"command": "uvx"}),
+        Some(&revision),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        result[REVISION_KEY].as_str().unwrap(),
+        current_revision(&config_path).await.unwrap()
+    );
+    let on_disk = JsonManager::read_json_file(&config_path).await.unwrap();
+    assert!(on_disk["mcpServers"].get("manual").is_some());
+    assert!(on_disk.get(REVISION_KEY).is_none());
+}
+
+#[tokio::test]
+async fn test_writes_without_revision_are_not_checked() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("mcp.json");
+    std::fs::write(&config_path, r#"{"mcpServers": {}}"#).unwrap();
+
+    let written = JsonManager::write_json_file_at(
+        &config_path,
+        &json!({"mcpServers": {"fetch": {"command": "uvx"}}}),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(written, current_revision(&config_path).await.unwrap());
+
+    let result = JsonManager::remove_mcp_server(&config_path, "cursor", "fetch", None)
+        .await
+        .unwrap();
+    assert_eq!(
+        result[REVISION_KEY],
+        current_revision(&config_path).await.unwrap()
+    );
+}
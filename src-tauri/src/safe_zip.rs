@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+// Every zip we unpack, downloaded catalogs and `.dxt` bundles alike, goes through here.
+// Sizes in the central directory can lie, so reads are capped by what actually comes
+// out, not only by what the archive declares.
+
+const MIB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ZipLimits {
+    pub max_entries: usize,
+    pub max_entry_size: u64,
+    pub max_total_size: u64,
+}
+
+pub(crate) const CATALOG_LIMITS: ZipLimits = ZipLimits {
+    max_entries: 1_000,
+    max_entry_size: 32 * MIB,
+    max_total_size: 32 * MIB,
+};
+
+pub(crate) const BUNDLE_LIMITS: ZipLimits = ZipLimits {
+    max_entries: 10_000,
+    max_entry_size: 256 * MIB,
+    max_total_size: 1024 * MIB,
+};
+
+/// An entry that was not extracted, and why
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RejectedEntry {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Open an archive, refusing ones with more entries than `limits` allow
+pub(crate) fn open_archive<R: Read + Seek>(
+    reader: R,
+    limits: &ZipLimits,
+) -> Result<ZipArchive<R>, String> {
+    let archive = ZipArchive::new(reader).map_err(|e| format!("Invalid zip: {}", e))?;
+    if archive.len() > limits.max_entries {
+        return Err(format!(
+            "Zip has {} entries, more than the {} allowed",
+            archive.len(),
+            limits.max_entries
+        ));
+    }
+    Ok(archive)
+}
+
+/// Read at most `limit` bytes, failing instead of truncating
+pub(crate) fn read_capped(reader: impl Read, limit: u64, name: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    reader
+        .take(limit + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+    if bytes.len() as u64 > limit {
+        return Err(format!("{} is larger than {} bytes", name, limit));
+    }
+    Ok(bytes)
+}
+
+/// Contents of the entry at `index`, within `max_entry_size`
+pub(crate) fn read_entry<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    index: usize,
+    limits: &ZipLimits,
+) -> Result<Vec<u8>, String> {
+    let entry = archive
+        .by_index(index)
+        .map_err(|e| format!("Failed to read zip entry: {}", e))?;
+    let name = entry.name().to_string();
+    read_capped(entry, limits.max_entry_size, &name)
+}
+
+fn is_symlink(mode: Option<u32>) -> bool {
+    mode.is_some_and(|mode| mode & 0o170000 == 0o120000)
+}
+
+/// Extract every entry below `target`. Entries that would land outside it, symlinks
+/// and oversized files are skipped and returned; going over the total size aborts.
+pub(crate) fn extract_all<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    target: &Path,
+    limits: &ZipLimits,
+) -> Result<Vec<RejectedEntry>, String> {
+    let mut rejected = Vec::new();
+    let mut total = 0u64;
+    for i in 0..archive.len() {
+        let entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read zip entry: {}", e))?;
+        let name = entry.name().to_string();
+        let reject = |reason: &str| RejectedEntry {
+            name: name.clone(),
+            reason: reason.to_string(),
+        };
+
+        let Some(relative) = entry.enclosed_name() else {
+            rejected.push(reject("path escapes the archive"));
+            continue;
+        };
+        if is_symlink(entry.unix_mode()) {
+            rejected.push(reject("symlinks are not allowed"));
+            continue;
+        }
+        let out = target.join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&out).map_err(|e| format!("Failed to create dir: {}", e))?;
+            continue;
+        }
+        if entry.size() > limits.max_entry_size {
+            rejected.push(reject(&format!(
+                "larger than {} bytes",
+                limits.max_entry_size
+            )));
+            continue;
+        }
+        if total + entry.size() > limits.max_total_size {
+            return Err(format!(
+                "Zip expands to more than {} bytes",
+                limits.max_total_size
+            ));
+        }
+
+        if let Some(parent) = out.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create dir: {}", e))?;
+        }
+        let mode = entry.unix_mode();
+        let mut file =
+            File::create(&out).map_err(|e| format!("Failed to create {}: {}", out.display(), e))?;
+        let written = io::copy(&mut entry.take(limits.max_entry_size + 1), &mut file)
+            .map_err(|e| format!("Failed to extract {}: {}", out.display(), e))?;
+        // The declared size was a lie
+        if written > limits.max_entry_size {
+            drop(file);
+            let _ = fs::remove_file(&out);
+            rejected.push(reject(&format!(
+                "larger than {} bytes",
+                limits.max_entry_size
+            )));
+            continue;
+        }
+        total += written;
+        if total > limits.max_total_size {
+            return Err(format!(
+                "Zip expands to more than {} bytes",
+                limits.max_total_size
+            ));
+        }
+
+        // Bundled binaries and scripts must stay executable, but never setuid
+        #[cfg(unix)]
+        if let Some(mode) = mode {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&out, fs::Permissions::from_mode(mode & 0o777));
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+    }
+    Ok(rejected)
+}
+
+/// `name` as a single directory name: separators, reserved characters and control
+/// characters become `-`, and leading/trailing dots, dashes and spaces are dropped.
+/// `None` when nothing usable is left.
+pub(crate) fn safe_component(name: &str) -> Option<String> {
+    let replaced: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let trimmed = replaced.trim_matches(|c: char| c == '.' || c == '-' || c.is_whitespace());
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
@@ -0,0 +1,61 @@
+// Tests for hardened zip extraction
+use crate::safe_zip::{extract_all, open_archive, safe_component, ZipLimits};
+use std::io::{Cursor, Write};
+use tempfile::tempdir;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+fn zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in files {
+        zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(content).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+const LIMITS: ZipLimits = ZipLimits {
+    max_entries: 4,
+    max_entry_size: 16,
+    max_total_size: 32,
+};
+
+#[test]
+fn test_rejects_unsafe_entries() {
+    let temp_dir = tempdir().unwrap();
+    let target = temp_dir.path().join("out");
+    let bytes = zip(&[
+        ("server/index.js", b"ok"),
+        ("../escape.txt", b"nope"),
+        ("/etc/passwd", b"nope"),
+        ("big.bin", &[0u8; 17]),
+    ]);
+    let mut archive = open_archive(Cursor::new(bytes), &LIMITS).unwrap();
+    let rejected = extract_all(&mut archive, &target, &LIMITS).unwrap();
+
+    let names: Vec<&str> = rejected.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["../escape.txt", "/etc/passwd", "big.bin"]);
+    assert!(target.join("server/index.js").exists());
+    assert!(!temp_dir.path().join("escape.txt").exists());
+    assert!(!target.join("big.bin").exists());
+}
+
+#[test]
+fn test_enforces_archive_limits() {
+    let temp_dir = tempdir().unwrap();
+    let many = zip(&[("a", b""), ("b", b""), ("c", b""), ("d", b""), ("e", b"")]);
+    assert!(open_archive(Cursor::new(many), &LIMITS).is_err());
+
+    let large = zip(&[("a", &[1u8; 16]), ("b", &[1u8; 16]), ("c", &[1u8; 16])]);
+    let mut archive = open_archive(Cursor::new(large), &LIMITS).unwrap();
+    let err = extract_all(&mut archive, temp_dir.path(), &LIMITS).unwrap_err();
+    assert!(err.contains("more than 32 bytes"), "{}", err);
+
+    assert_eq!(safe_component("../../etc").as_deref(), Some("etc"));
+    assert_eq!(safe_component("jane/files").as_deref(), Some("jane-files"));
+    assert_eq!(
+        safe_component("Weather Tools").as_deref(),
+        Some("Weather Tools")
+    );
+    assert_eq!(safe_component(".."), None);
+}
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+
+/// A client whose servers live inside a larger document (or several files) that
+/// we must not rewrite wholesale. Servers cross this boundary in the common
+/// `mcpServers` shape; each backend maps them to and from its own format.
+#[async_trait]
+pub trait ServerStore: Send + Sync {
+    fn client(&self) -> &str;
+
+    /// Enabled servers in the common shape
+    async fn read_servers(&self) -> Result<Map<String, Value>, String>;
+
+    /// Disabled servers in the common shape
+    async fn list_disabled(&self) -> Result<Map<String, Value>, String>;
+
+    /// Insert or replace a server
+    async fn upsert(&self, name: &str, cfg: Value) -> Result<(), String>;
+
+    async fn remove(&self, name: &str) -> Result<(), String>;
+
+    async fn set_enabled(&self, name: &str, enabled: bool) -> Result<(), String>;
+
+    /// Insert or replace a server in its disabled state
+    async fn update_disabled(&self, name: &str, cfg: Value) -> Result<(), String>;
+
+    /// Replace every portable server with `active` + `disabled`
+    async fn replace_all(
+        &self,
+        active: &Map<String, Value>,
+        disabled: &Map<String, Value>,
+    ) -> Result<(), String>;
+
+    /// `{ "mcpServers": ..., "__disabled": ... }`, the shape `read_json_file` returns
+    async fn read_document(&self) -> Result<Value, String> {
+        Ok(json!({
+            "mcpServers": self.read_servers().await?,
+            "__disabled": self.list_disabled().await?,
+        }))
+    }
+}
+
+/// Backends for clients that are not plain `mcpServers` JSON files, including the
+/// declarative ones from `client_defs`
+pub fn store_for(client: &str, path: Option<&str>) -> Option<Box<dyn ServerStore>> {
+    match client {
+        "zed" => Some(Box::new(crate::zed::ZedStore::new(path))),
+        "goose" => Some(Box::new(crate::goose::GooseStore::new(path))),
+        "continue" => Some(Box::new(crate::continue_dev::ContinueStore::new(path))),
+        _ => crate::client_defs::definition(client).map(|def| {
+            Box::new(crate::client_defs::DefinedStore::new(def, path)) as Box<dyn ServerStore>
+        }),
+    }
+}
@@ -0,0 +1,126 @@
+use crate::discovery::{
+    count_codex_servers, count_json_servers, count_store_servers, discovered, DiscoveredClient,
+};
+use crate::store::store_for;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::task;
+
+/// Project-level config files, relative to the project dir, and the client they belong to
+const PROJECT_MARKERS: [(&str, &str); 8] = [
+    (".cursor/mcp.json", "cursor"),
+    (".vscode/mcp.json", "copilot"),
+    (".roo/mcp.json", "roo_code"),
+    (".mcp.json", "claude_code"),
+    (".gemini/settings.json", "gemini"),
+    (".codex/config.toml", "codex"),
+    (".zed/settings.json", "zed"),
+    (".continue", "continue"),
+];
+
+/// Never descended into; other hidden dirs are skipped too, markers are probed directly
+const SKIPPED_DIRS: [&str; 2] = ["node_modules", ".git"];
+
+const DEFAULT_MAX_DEPTH: usize = 8;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkspaceProject {
+    pub path: String,
+    pub configs: Vec<DiscoveredClient>,
+}
+
+/// Project dir -> (client, config path) for every marker found under `root`
+fn find_project_configs(root: &Path, max_depth: usize) -> Vec<(PathBuf, Vec<(String, PathBuf)>)> {
+    let mut markers: Vec<(String, String)> = PROJECT_MARKERS
+        .iter()
+        .map(|(relative, client)| (relative.to_string(), client.to_string()))
+        .collect();
+    for client in crate::client_defs::defined_clients() {
+        let project =
+            crate::client_defs::definition(client).and_then(|def| def.paths.project.clone());
+        if let Some(project) = project {
+            markers.push((project, client.to_string()));
+        }
+    }
+
+    let mut projects = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        let found: Vec<(String, PathBuf)> = markers
+            .iter()
+            .map(|(relative, client)| (client.clone(), dir.join(relative)))
+            .filter(|(_, path)| path.exists())
+            .collect();
+        if !found.is_empty() {
+            projects.push((dir.clone(), found));
+        }
+
+        if depth >= max_depth {
+            continue;
+        }
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                println!("[WorkspaceScan] skip {}: {}", dir.display(), e);
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            // `file_type` does not follow symlinks, so linked trees are not walked twice
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            let name = entry.file_name().to_string_lossy().to_string();
+            if is_dir && !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                pending.push((entry.path(), depth + 1));
+            }
+        }
+    }
+
+    projects.sort_by(|a, b| a.0.cmp(&b.0));
+    projects
+}
+
+/// Walk `root` and report every project-level MCP config, grouped by project dir
+#[tauri::command]
+pub async fn scan_workspace(
+    root: String,
+    max_depth: Option<usize>,
+) -> Result<Vec<WorkspaceProject>, String> {
+    let root_path = PathBuf::from(&root);
+    if !root_path.is_dir() {
+        return Err(format!("Workspace '{}' is not a directory", root));
+    }
+    let max_depth = max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
+    let found = task::spawn_blocking(move || find_project_configs(&root_path, max_depth))
+        .await
+        .map_err(|e| format!("Workspace scan failed: {}", e))?;
+
+    let mut projects = Vec::new();
+    for (dir, configs) in found {
+        let dir_str = dir.to_string_lossy().to_string();
+        let mut reports = Vec::new();
+        for (client, path) in configs {
+            let counts = match client.as_str() {
+                "codex" => count_codex_servers(&path),
+                // Claude Code's project file is plain `mcpServers` JSON
+                "claude_code" => count_json_servers("claude", &path),
+                _ => match store_for(&client, Some(&dir_str)) {
+                    Some(store) => count_store_servers(store.as_ref()).await,
+                    None => count_json_servers(&client, &path),
+                },
+            };
+            reports.push(discovered(&client, None, &path, counts));
+        }
+        projects.push(WorkspaceProject {
+            path: dir_str,
+            configs: reports,
+        });
+    }
+
+    println!(
+        "[WorkspaceScan] {} projects with MCP configs under {}",
+        projects.len(),
+        root
+    );
+    Ok(projects)
+}
@@ -0,0 +1,45 @@
+// Tests for the workspace scan
+use crate::workspace_scan::scan_workspace;
+use std::fs;
+
+#[tokio::test]
+async fn test_scan_groups_configs_and_skips_node_modules() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let write = |relative: &str, content: &str| {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    };
+    write(
+        "app/.cursor/mcp.json",
+        r#"{"mcpServers": {"a": {"command": "npx"}}}"#,
+    );
+    write(
+        "app/.vscode/mcp.json",
+        r#"{"servers": {"b": {"url": "https://x"}}, "inputs": []}"#,
+    );
+    write(
+        "libs/tool/.codex/config.toml",
+        "[mcp_servers.c]\ntype = \"stdio\"\ncommand = \"uvx\"\nargs = []\n",
+    );
+    write(
+        "app/node_modules/pkg/.cursor/mcp.json",
+        r#"{"mcpServers": {}}"#,
+    );
+
+    let projects = scan_workspace(root.to_string_lossy().to_string(), None)
+        .await
+        .unwrap();
+    assert_eq!(projects.len(), 2);
+
+    let app = &projects[0];
+    assert!(app.path.ends_with("app"));
+    let clients: Vec<&str> = app.configs.iter().map(|c| c.client.as_str()).collect();
+    assert_eq!(clients, vec!["cursor", "copilot"]);
+    assert!(app.configs.iter().all(|c| c.server_count == 1));
+
+    assert!(projects[1].path.ends_with("tool"));
+    assert_eq!(projects[1].configs[0].client, "codex");
+    assert_eq!(projects[1].configs[0].server_count, 1);
+}
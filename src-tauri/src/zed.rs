@@ -0,0 +1,519 @@
+use crate::client::ClientConfig;
+use crate::store::ServerStore;
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+// Zed keeps MCP servers under `context_servers` in a JSONC settings file:
+// { "context_servers": { "name": { "source": "custom", "command": "npx", "args": [], "env": {} } } }
+// Older builds nest the command: { "command": { "path": "npx", "args": [], "env": {} } }
+
+const SERVERS_KEY: &str = "context_servers";
+
+static ZED_CFG_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Convert a Zed context server into the common `mcpServers` shape.
+/// Extension-provided servers have no portable form and yield `None`.
+pub(crate) fn zed_to_common(server: &Value) -> Option<Value> {
+    let obj = server.as_object()?;
+    if obj.get("source").and_then(|s| s.as_str()) == Some("extension") {
+        return None;
+    }
+
+    let mut out = Map::new();
+    match obj.get("command") {
+        Some(Value::Object(cmd)) => {
+            out.insert("type".into(), json!("stdio"));
+            out.insert("command".into(), cmd.get("path").cloned()?);
+            out.insert(
+                "args".into(),
+                cmd.get("args").cloned().unwrap_or_else(|| json!([])),
+            );
+            if let Some(env) = cmd.get("env").filter(|e| e.is_object()) {
+                out.insert("env".into(), env.clone());
+            }
+        }
+        Some(Value::String(command)) => {
+            out.insert("type".into(), json!("stdio"));
+            out.insert("command".into(), json!(command));
+            out.insert(
+                "args".into(),
+                obj.get("args").cloned().unwrap_or_else(|| json!([])),
+            );
+            if let Some(env) = obj.get("env").filter(|e| e.is_object()) {
+                out.insert("env".into(), env.clone());
+            }
+        }
+        _ => {
+            let url = obj.get("url").and_then(|u| u.as_str())?;
+            out.insert("type".into(), json!("http"));
+            out.insert("url".into(), json!(url));
+            if let Some(headers) = obj.get("headers").filter(|h| h.is_object()) {
+                out.insert("headers".into(), headers.clone());
+            }
+        }
+    }
+    Some(Value::Object(out))
+}
+
+/// Convert a common server config into Zed's flat `source: custom` shape,
+/// carrying over Zed-only keys such as `settings` from the entry being replaced.
+pub(crate) fn common_to_zed(cfg: &Value, previous: Option<&Value>) -> Result<Value, String> {
+    let mut out = json!({"source": "custom"});
+
+    if let Some(command) = cfg.get("command").and_then(|c| c.as_str()) {
+        out["command"] = json!(command);
+        out["args"] = cfg.get("args").cloned().unwrap_or_else(|| json!([]));
+        if let Some(env) = cfg.get("env").filter(|e| e.is_object()) {
+            out["env"] = env.clone();
+        }
+    } else if let Some(url) = cfg.get("url").and_then(|u| u.as_str()) {
+        out["url"] = json!(url);
+        if let Some(headers) = cfg.get("headers").filter(|h| h.is_object()) {
+            out["headers"] = headers.clone();
+        }
+    } else {
+        return Err("Zed servers need a `command` or `url`".to_string());
+    }
+
+    if let Some(settings) = previous.and_then(|p| p.get("settings")) {
+        out["settings"] = settings.clone();
+    }
+
+    let disabled = cfg.get("disabled").and_then(|d| d.as_bool()) == Some(true)
+        || cfg.get("isActive").and_then(|a| a.as_bool()) == Some(false)
+        || cfg.get("enabled").and_then(|e| e.as_bool()) == Some(false);
+    if disabled {
+        out["enabled"] = json!(false);
+    }
+    Ok(out)
+}
+
+fn is_enabled(server: &Value) -> bool {
+    server
+        .get("enabled")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(true)
+}
+
+// --- Minimal JSONC handling: enough to parse the file and splice one root member ---
+
+fn skip_ws_and_comments(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b' ' | b'\t' | b'\r' | b'\n' => i += 1,
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i = (i + 2).min(bytes.len());
+            }
+            _ => break,
+        }
+    }
+    i
+}
+
+/// Index just past the closing quote of the string starting at `start`
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Index just past the JSONC value starting at `start`
+fn value_end(bytes: &[u8], start: usize) -> usize {
+    match bytes.get(start) {
+        Some(b'"') => string_end(bytes, start),
+        Some(b'{') | Some(b'[') => {
+            let mut depth = 0usize;
+            let mut i = start;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = string_end(bytes, i);
+                        continue;
+                    }
+                    b'/' if matches!(bytes.get(i + 1), Some(b'/') | Some(b'*')) => {
+                        i = skip_ws_and_comments(bytes, i);
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return i + 1;
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            bytes.len()
+        }
+        _ => {
+            let mut i = start;
+            while i < bytes.len()
+                && !matches!(
+                    bytes[i],
+                    b',' | b'}' | b']' | b' ' | b'\t' | b'\r' | b'\n' | b'/'
+                )
+            {
+                i += 1;
+            }
+            i
+        }
+    }
+}
+
+/// Where a root member lives in the raw text
+enum MemberLocation {
+    /// Byte span of the member's value
+    Found(usize, usize),
+    /// Member absent: position after the last member (if any) and of the closing brace
+    Missing {
+        last_member_end: Option<usize>,
+        close: usize,
+    },
+}
+
+fn locate_root_member(text: &str, key: &str) -> Result<MemberLocation, String> {
+    let bytes = text.as_bytes();
+    let mut i = skip_ws_and_comments(bytes, 0);
+    if bytes.get(i) != Some(&b'{') {
+        return Err("Settings root is not an object".to_string());
+    }
+    i += 1;
+
+    let mut last_member_end = None;
+    loop {
+        i = skip_ws_and_comments(bytes, i);
+        match bytes.get(i) {
+            Some(b'}') => {
+                return Ok(MemberLocation::Missing {
+                    last_member_end,
+                    close: i,
+                })
+            }
+            Some(b'"') => {
+                let key_end = string_end(bytes, i);
+                let member_key: String = serde_json::from_str(&text[i..key_end])
+                    .map_err(|e| format!("Invalid settings key: {}", e))?;
+                i = skip_ws_and_comments(bytes, key_end);
+                if bytes.get(i) != Some(&b':') {
+                    return Err(format!("Expected ':' after \"{}\"", member_key));
+                }
+                let start = skip_ws_and_comments(bytes, i + 1);
+                let end = value_end(bytes, start);
+                if member_key == key {
+                    return Ok(MemberLocation::Found(start, end));
+                }
+                last_member_end = Some(end);
+                i = skip_ws_and_comments(bytes, end);
+                if bytes.get(i) == Some(&b',') {
+                    i += 1;
+                }
+            }
+            _ => return Err("Malformed settings file".to_string()),
+        }
+    }
+}
+
+/// Strip comments and trailing commas so serde_json can parse JSONC
+pub(crate) fn strip_jsonc(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let end = string_end(bytes, i);
+                out.extend_from_slice(&bytes[i..end]);
+                i = end;
+            }
+            b'/' if matches!(bytes.get(i + 1), Some(b'/') | Some(b'*')) => {
+                let end = skip_ws_and_comments(bytes, i);
+                out.push(b' ');
+                i = end;
+            }
+            b',' => {
+                let next = skip_ws_and_comments(bytes, i + 1);
+                if !matches!(bytes.get(next), Some(b'}') | Some(b']')) {
+                    out.push(b',');
+                }
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(out).unwrap_or_default()
+}
+
+fn parse_settings(text: &str) -> Result<Value, String> {
+    if text.trim().is_empty() {
+        return Ok(json!({}));
+    }
+    serde_json::from_str(&strip_jsonc(text))
+        .map_err(|e| format!("Failed to parse Zed settings: {}", e))
+}
+
+/// Replace (or insert) the `context_servers` member, leaving the rest of the text untouched
+pub(crate) fn splice_servers(text: &str, servers: &Map<String, Value>) -> Result<String, String> {
+    let pretty = serde_json::to_string_pretty(&Value::Object(servers.clone()))
+        .map_err(|e| format!("Failed to serialize Zed servers: {}", e))?;
+    // Indent nested lines one level since the value sits inside the root object
+    let value = pretty.replace('\n', "\n  ");
+
+    if text.trim().is_empty() {
+        return Ok(format!("{{\n  \"{}\": {}\n}}\n", SERVERS_KEY, value));
+    }
+
+    match locate_root_member(text, SERVERS_KEY)? {
+        MemberLocation::Found(start, end) => {
+            Ok(format!("{}{}{}", &text[..start], value, &text[end..]))
+        }
+        MemberLocation::Missing {
+            last_member_end: Some(pos),
+            ..
+        } => Ok(format!(
+            "{},\n  \"{}\": {}{}",
+            &text[..pos],
+            SERVERS_KEY,
+            value,
+            &text[pos..]
+        )),
+        MemberLocation::Missing {
+            last_member_end: None,
+            close,
+        } => Ok(format!(
+            "{}\n  \"{}\": {}\n{}",
+            &text[..close],
+            SERVERS_KEY,
+            value,
+            &text[close..]
+        )),
+    }
+}
+
+async fn load_text(config_path: &Path) -> Result<String, String> {
+    if !config_path.exists() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(config_path)
+        .await
+        .map_err(|e| format!("Failed to read Zed settings: {}", e))
+}
+
+async fn persist_text(config_path: &Path, text: &str) -> Result<(), String> {
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let tmp_path = config_path.with_extension("json.tmp");
+    let mut file = fs::File::create(&tmp_path)
+        .await
+        .map_err(|e| format!("Failed to create temp file: {}", e))?;
+    file.write_all(text.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write temp file: {}", e))?;
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to flush temp file: {}", e))?;
+    drop(file);
+    crate::config_watcher::note_self_write(config_path, text.as_bytes());
+    fs::rename(&tmp_path, config_path)
+        .await
+        .map_err(|e| format!("Failed to rename temp file: {}", e))
+}
+
+async fn read_context_servers(config_path: &Path) -> Result<Map<String, Value>, String> {
+    let settings = parse_settings(&load_text(config_path).await?)?;
+    Ok(settings
+        .get(SERVERS_KEY)
+        .and_then(|s| s.as_object())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Load, mutate and write back `context_servers` under the Zed config lock
+async fn edit_context_servers<F>(config_path: &Path, edit: F) -> Result<(), String>
+where
+    F: FnOnce(&mut Map<String, Value>) -> Result<(), String>,
+{
+    let _guard = ZED_CFG_LOCK.lock().await;
+    let text = load_text(config_path).await?;
+    let settings = parse_settings(&text)?;
+    let mut servers = settings
+        .get(SERVERS_KEY)
+        .and_then(|s| s.as_object())
+        .cloned()
+        .unwrap_or_default();
+    edit(&mut servers)?;
+    persist_text(config_path, &splice_servers(&text, &servers)?).await
+}
+
+fn to_common_map(servers: &Map<String, Value>, enabled: bool) -> Map<String, Value> {
+    servers
+        .iter()
+        .filter(|(_, server)| is_enabled(server) == enabled)
+        .filter_map(|(name, server)| zed_to_common(server).map(|cfg| (name.clone(), cfg)))
+        .collect()
+}
+
+pub async fn read_mcp_servers(config_path: &Path) -> Result<Map<String, Value>, String> {
+    Ok(to_common_map(
+        &read_context_servers(config_path).await?,
+        true,
+    ))
+}
+
+pub async fn list_disabled(config_path: &Path) -> Result<Map<String, Value>, String> {
+    Ok(to_common_map(
+        &read_context_servers(config_path).await?,
+        false,
+    ))
+}
+
+/// Insert or replace a server given in the common shape
+pub async fn add_mcp_server(config_path: &Path, name: &str, cfg: Value) -> Result<(), String> {
+    edit_context_servers(config_path, |servers| {
+        let server = common_to_zed(&cfg, servers.get(name))?;
+        servers.insert(name.to_string(), server);
+        Ok(())
+    })
+    .await
+}
+
+pub async fn delete_mcp_server(config_path: &Path, name: &str) -> Result<(), String> {
+    edit_context_servers(config_path, |servers| {
+        servers
+            .remove(name)
+            .map(|_| ())
+            .ok_or_else(|| format!("MCP server '{}' not found", name))
+    })
+    .await
+}
+
+async fn set_enabled(config_path: &Path, name: &str, enabled: bool) -> Result<(), String> {
+    edit_context_servers(config_path, |servers| {
+        let server = servers
+            .get_mut(name)
+            .and_then(|s| s.as_object_mut())
+            .ok_or_else(|| format!("MCP server '{}' not found", name))?;
+        if enabled {
+            server.remove("enabled");
+        } else {
+            server.insert("enabled".into(), json!(false));
+        }
+        Ok(())
+    })
+    .await
+}
+
+pub async fn update_disabled(config_path: &Path, name: &str, cfg: Value) -> Result<(), String> {
+    edit_context_servers(config_path, |servers| {
+        let mut server = common_to_zed(&cfg, servers.get(name))?;
+        server["enabled"] = json!(false);
+        servers.insert(name.to_string(), server);
+        Ok(())
+    })
+    .await
+}
+
+/// Replace every custom server with `active` + `disabled` (common shape).
+/// Extension-provided servers are kept since they cannot be expressed elsewhere.
+pub async fn replace_servers(
+    config_path: &Path,
+    active: &Map<String, Value>,
+    disabled: &Map<String, Value>,
+) -> Result<(), String> {
+    edit_context_servers(config_path, |servers| {
+        let previous = std::mem::take(servers);
+        for (name, server) in &previous {
+            if zed_to_common(server).is_none() {
+                servers.insert(name.clone(), server.clone());
+            }
+        }
+        for (name, cfg) in active {
+            servers.insert(name.clone(), common_to_zed(cfg, previous.get(name))?);
+        }
+        for (name, cfg) in disabled {
+            let mut server = common_to_zed(cfg, previous.get(name))?;
+            server["enabled"] = json!(false);
+            servers.insert(name.clone(), server);
+        }
+        Ok(())
+    })
+    .await
+}
+
+pub struct ZedStore {
+    path: PathBuf,
+}
+
+impl ZedStore {
+    pub fn new(path: Option<&str>) -> Self {
+        Self {
+            path: ClientConfig::new("zed", path).get_path().to_path_buf(),
+        }
+    }
+}
+
+#[async_trait]
+impl ServerStore for ZedStore {
+    fn client(&self) -> &str {
+        "zed"
+    }
+
+    async fn read_servers(&self) -> Result<Map<String, Value>, String> {
+        read_mcp_servers(&self.path).await
+    }
+
+    async fn list_disabled(&self) -> Result<Map<String, Value>, String> {
+        list_disabled(&self.path).await
+    }
+
+    async fn upsert(&self, name: &str, cfg: Value) -> Result<(), String> {
+        add_mcp_server(&self.path, name, cfg).await
+    }
+
+    async fn remove(&self, name: &str) -> Result<(), String> {
+        delete_mcp_server(&self.path, name).await
+    }
+
+    async fn set_enabled(&self, name: &str, enabled: bool) -> Result<(), String> {
+        set_enabled(&self.path, name, enabled).await
+    }
+
+    async fn update_disabled(&self, name: &str, cfg: Value) -> Result<(), String> {
+        update_disabled(&self.path, name, cfg).await
+    }
+
+    async fn replace_all(
+        &self,
+        active: &Map<String, Value>,
+        disabled: &Map<String, Value>,
+    ) -> Result<(), String> {
+        replace_servers(&self.path, active, disabled).await
+    }
+}
@@ -0,0 +1,74 @@
+// Tests for Zed settings splicing and shape mapping
+use crate::store::ServerStore;
+use crate::zed::{common_to_zed, splice_servers, strip_jsonc, zed_to_common, ZedStore};
+use serde_json::{json, Value};
+
+#[test]
+fn test_splice_keeps_comments_and_other_settings() {
+    let text = r#"{
+  // theme picked by hand
+  "theme": "One Dark",
+  "context_servers": {
+    "old": { "command": { "path": "old-bin", "args": [] } }
+  },
+  /* trailing */ "vim_mode": true,
+}
+"#;
+    let servers = json!({"new": {"source": "custom", "command": "npx", "args": ["-y", "pkg"]}});
+    let updated = splice_servers(text, servers.as_object().unwrap()).unwrap();
+
+    assert!(updated.contains("// theme picked by hand"));
+    assert!(updated.contains("/* trailing */ \"vim_mode\": true,"));
+    assert!(!updated.contains("old-bin"));
+    let parsed: Value = serde_json::from_str(&strip_jsonc(&updated)).unwrap();
+    assert_eq!(parsed["theme"], "One Dark");
+    assert_eq!(parsed["context_servers"]["new"]["command"], "npx");
+}
+
+#[test]
+fn test_splice_inserts_missing_section() {
+    let text = "{\n  \"theme\": \"One Dark\" // comment\n}\n";
+    let servers = json!({"a": {"source": "custom", "url": "https://x"}});
+    let updated = splice_servers(text, servers.as_object().unwrap()).unwrap();
+    let parsed: Value = serde_json::from_str(&strip_jsonc(&updated)).unwrap();
+    assert_eq!(parsed["theme"], "One Dark");
+    assert_eq!(parsed["context_servers"]["a"]["url"], "https://x");
+}
+
+#[test]
+fn test_shape_mapping() {
+    let nested = json!({"command": {"path": "uvx", "args": ["srv"], "env": {"K": "V"}}});
+    assert_eq!(
+        zed_to_common(&nested).unwrap(),
+        json!({"type": "stdio", "command": "uvx", "args": ["srv"], "env": {"K": "V"}})
+    );
+    assert!(zed_to_common(&json!({"source": "extension", "settings": {}})).is_none());
+
+    let previous = json!({"source": "custom", "command": "a", "settings": {"x": 1}});
+    let zed = common_to_zed(
+        &json!({"type": "stdio", "command": "b", "args": [], "disabled": true}),
+        Some(&previous),
+    )
+    .unwrap();
+    assert_eq!(
+        zed,
+        json!({"source": "custom", "command": "b", "args": [], "settings": {"x": 1}, "enabled": false})
+    );
+}
+
+#[tokio::test]
+async fn test_store_reads_discovered_settings_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("settings.json");
+    std::fs::write(
+        &path,
+        r#"{"context_servers": {"fetch": {"command": {"path": "uvx", "args": ["mcp-server-fetch"]}}}}"#,
+    )
+    .unwrap();
+
+    let store = ZedStore::new(Some(path.to_str().unwrap()));
+    assert_eq!(
+        store.read_servers().await.unwrap()["fetch"]["command"],
+        "uvx"
+    );
+}
@@ -3,6 +3,7 @@ import { Dialog, DialogContent, DialogDescription, DialogFooter, DialogHeader, D
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
+import { Textarea } from "@/components/ui/textarea";
 import { Globe, Plus, Terminal } from "lucide-react";
 import { useState } from "react";
 
@@ -12,6 +13,7 @@ interface ServerFormData {
   url: string;
   command: string;
   args: string;
+  headers?: Record<string, string>;
 }
 
 interface AddServerDialogProps {
@@ -31,9 +33,25 @@ export default function AddServerDialog({ onAddServer, open: controlledOpen, onO
     command: "",
     args: ""
   });
+  const [headersJson, setHeadersJson] = useState("");
+  const [headersError, setHeadersError] = useState<string | null>(null);
 
   const handleAddServer = async () => {
-    const success = await onAddServer(formData);
+    let headers: Record<string, string> | undefined;
+    if ((formData.type === "http" || formData.type === "sse") && headersJson.trim()) {
+      try {
+        headers = JSON.parse(headersJson);
+      } catch {
+        setHeadersError("Headers must be a JSON object");
+        return;
+      }
+      if (!headers || typeof headers !== "object" || Array.isArray(headers)) {
+        setHeadersError("Headers must be a JSON object");
+        return;
+      }
+    }
+    setHeadersError(null);
+    const success = await onAddServer({ ...formData, headers });
     if (success) {
       setOpen(false);
       setFormData({
@@ -43,6 +61,7 @@ export default function AddServerDialog({ onAddServer, open: controlledOpen, onO
         command: "",
         args: ""
       });
+      setHeadersJson("");
     }
   };
 
@@ -119,6 +138,25 @@ export default function AddServerDialog({ onAddServer, open: controlledOpen, onO
             </div>
           )}
 
+          {(formData.type === "http" || formData.type === "sse") && (
+            <div className="grid gap-2">
+              <Label htmlFor="headers">Headers (optional, JSON)</Label>
+              <Textarea
+                id="headers"
+                placeholder={'{"Authorization": "Bearer <token>"}'}
+                value={headersJson}
+                onChange={(e) => {
+                  setHeadersJson(e.target.value);
+                  setHeadersError(null);
+                }}
+                className="font-mono text-sm"
+              />
+              {headersError && (
+                <p className="text-sm text-destructive">{headersError}</p>
+              )}
+            </div>
+          )}
+
           {formData.type === "stdio" && (
             <>
               <div className="grid gap-2">
@@ -11,6 +11,7 @@ interface Server {
   command?: string;
   args?: string[];
   env?: Record<string, any>;
+  headers?: Record<string, any>;
 }
 
 interface ServerDetailsDialogProps {
@@ -88,6 +89,19 @@ export default function ServerDetailsDialog({ open, onOpenChange, server }: Serv
               </div>
             )}
 
+            {server.headers && Object.keys(server.headers).length > 0 && (
+              <div>
+                <Label className="text-sm font-medium">Headers</Label>
+                <div className="mt-1 bg-muted p-2 rounded">
+                  {Object.entries(server.headers).map(([key, value]) => (
+                    <div key={key} className="text-sm font-mono">
+                      {key}: {String(value)}
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
+
             {server.env && Object.keys(server.env).length > 0 && (
               <div>
                 <Label className="text-sm font-medium">Environment Variables</Label>
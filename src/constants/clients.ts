@@ -73,6 +73,48 @@ const clients: ClientInfo[] = [
     desc: "Neovim plugin for Model Context Protocol integration and server management",
     requiredTier: "FREE"
   },
+  {
+    value: "gemini",
+    label: "Gemini CLI",
+    url: "https://github.com/google-gemini/gemini-cli",
+    desc: "Google's open-source AI agent for the terminal with MCP server support",
+    requiredTier: "LIFETIME"
+  },
+  {
+    value: "zed",
+    label: "Zed",
+    url: "https://zed.dev/",
+    desc: "High-performance collaborative code editor with MCP context servers",
+    requiredTier: "LIFETIME"
+  },
+  {
+    value: "goose",
+    label: "Goose",
+    url: "https://block.github.io/goose/",
+    desc: "Open-source on-machine AI agent with MCP extensions",
+    requiredTier: "LIFETIME"
+  },
+  {
+    value: "continue",
+    label: "Continue",
+    url: "https://continue.dev/",
+    desc: "Open-source AI code assistant for VS Code and JetBrains",
+    requiredTier: "LIFETIME"
+  },
+  {
+    value: "opencode",
+    label: "OpenCode",
+    url: "https://opencode.ai/",
+    desc: "Open-source AI coding agent for the terminal",
+    requiredTier: "LIFETIME"
+  },
+  {
+    value: "lmstudio",
+    label: "LM Studio",
+    url: "https://lmstudio.ai/",
+    desc: "Desktop app for running local LLMs with MCP tool support",
+    requiredTier: "LIFETIME"
+  },
 ];
 
 export const availableClients = clients;
@@ -10,6 +10,8 @@ interface ClaudeCodeServer {
   command?: string;
   args?: string[];
   env?: Record<string, string>;
+  headers?: Record<string, string>;
+  [key: string]: unknown;
 }
 
 interface AddServerRequest {
@@ -19,6 +21,7 @@ interface AddServerRequest {
   command?: string;
   args?: string[];
   env?: Record<string, string>;
+  headers?: Record<string, string>;
 }
 
 interface ServerFormData {
@@ -28,6 +31,7 @@ interface ServerFormData {
   command: string;
   args: string;
   env?: Record<string, string>;
+  headers?: Record<string, string>;
 }
 
 export function useClaudeCodeManagement() {
@@ -81,7 +85,7 @@ export function useClaudeCodeManagement() {
 
   const addServer = async (formData: ServerFormData) => {
     try {
-      const { name, type, url, command, args, env } = formData;
+      const { name, type, url, command, args, env, headers } = formData;
       
       if (!name.trim()) {
         toast({
@@ -107,6 +111,9 @@ export function useClaudeCodeManagement() {
           return false;
         }
         request.url = url.trim();
+        if (headers && Object.keys(headers).length > 0) {
+          request.headers = headers;
+        }
       } else if (type === "stdio") {
         if (!command.trim()) {
           toast({
@@ -1,2 +1,2 @@
-export const needspathClient = ["cursor", "custom", "vscode"];
+export const needspathClient = ["cursor", "custom", "vscode", "gemini", "continue", "copilot", "opencode"];
 export const mustHavePathClients = ["custom", "vscode"];
\ No newline at end of file